@@ -1,8 +1,20 @@
 use std::cmp::max;
 
-use itertools::Itertools;
+use advent_of_code::parse::{self, Line, ParseError};
+use advent_of_code::template::grammar::Grammar;
+use anyhow::Result;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, grammar = input_grammar());
+
+fn input_grammar() -> Grammar {
+    let cubes = r"\d+ (red|green|blue)";
+    let round = format!("{cubes}(, {cubes})*");
+
+    Grammar::lines(
+        &format!(r"Game \d+: {round}(; {round})*"),
+        "Game N: N <color>, ...; ...",
+    )
+}
 
 struct Round {
     red: u32,
@@ -26,93 +38,83 @@ struct Game {
 }
 
 impl Game {
-    fn from_str(s: &str) -> Self {
-        let mut parts = s.split(':');
-        let game = parts
-            .next()
-            .expect("Always a game label")
-            .split_ascii_whitespace()
-            .nth(1)
-            .expect("Always a game id")
-            .parse()
-            .expect("Always an integer");
-
-        let rounds = parts.next().expect("Always rounds").trim().split("; ");
+    fn from_line(line: Line) -> Result<Self, ParseError> {
+        let (game, rounds) = line
+            .split_once(": ")
+            .ok_or_else(|| line.error("expected \"Game N: ...\""))?;
+        let [id] = game.array()?;
 
         let rounds = rounds
+            .split("; ")
             .map(|round| {
                 let mut red = 0;
                 let mut green = 0;
                 let mut blue = 0;
 
-                round.split(", ").for_each(|cube_combo| {
-                    let mut round_parts = cube_combo.split_ascii_whitespace();
-
-                    let num: u32 = round_parts
-                        .next()
-                        .expect("Always a number")
-                        .parse()
-                        .expect("Always integer");
-                    let color = round_parts.next().expect("Always a color");
+                for cube_combo in round.split(", ") {
+                    let [num] = cube_combo.array::<u32, 1>()?;
 
-                    match color {
+                    match cube_combo
+                        .as_str()?
+                        .trim_start_matches(|c: char| !c.is_alphabetic())
+                    {
                         "red" => red += num,
                         "green" => green += num,
                         "blue" => blue += num,
-                        _ => {
-                            panic!("Invalid color {color}!")
-                        }
+                        color => return Err(line.error(format!("invalid color {color}"))),
                     }
-                });
+                }
 
-                Round { red, green, blue }
+                Ok(Round { red, green, blue })
             })
-            .collect_vec();
+            .collect::<Result<_, ParseError>>()?;
 
-        Self { id: game, rounds }
+        Ok(Self { id, rounds })
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = input.lines().map(Game::from_str);
-
-    Some(
-        games
-            .map(|game| {
-                let possible = game
-                    .rounds
-                    .iter()
-                    .any(|round| round.red > 12 || round.green > 13 || round.blue > 14);
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse::lines(input).map(Game::from_line).collect()
+}
 
-                if !possible {
-                    game.id
-                } else {
-                    0
-                }
-            })
-            .sum(),
-    )
+pub fn part_one(input: &str) -> Result<u32> {
+    let games = parse_games(input)?;
+
+    Ok(games
+        .iter()
+        .map(|game| {
+            let possible = game
+                .rounds
+                .iter()
+                .any(|round| round.red > 12 || round.green > 13 || round.blue > 14);
+
+            if !possible {
+                game.id
+            } else {
+                0
+            }
+        })
+        .sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = input.lines().map(Game::from_str);
-
-    Some(
-        games
-            .map(|game| {
-                let min_game_config =
-                    game.rounds
-                        .iter()
-                        .fold(Round::zeros(), |min_cubes, round| Round {
-                            red: max(min_cubes.red, round.red),
-                            green: max(min_cubes.green, round.green),
-                            blue: max(min_cubes.blue, round.blue),
-                        });
-
-                min_game_config.red * min_game_config.green * min_game_config.blue
-            })
-            .sum(),
-    )
+pub fn part_two(input: &str) -> Result<u32> {
+    let games = parse_games(input)?;
+
+    Ok(games
+        .iter()
+        .map(|game| {
+            let min_game_config =
+                game.rounds
+                    .iter()
+                    .fold(Round::zeros(), |min_cubes, round| Round {
+                        red: max(min_cubes.red, round.red),
+                        green: max(min_cubes.green, round.green),
+                        blue: max(min_cubes.blue, round.blue),
+                    });
+
+            min_game_config.red * min_game_config.green * min_game_config.blue
+        })
+        .sum())
 }
 
 #[cfg(test)]
@@ -120,14 +122,25 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY));
-        assert_eq!(result, Some(8));
+    fn test_part_one() -> Result<()> {
+        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY))?;
+        assert_eq!(result, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY))?;
+        assert_eq!(result, 2286);
+        Ok(())
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
-        assert_eq!(result, Some(2286));
+    fn test_invalid_color() {
+        let result = part_one("Game 1: 3 blue\nGame 2: 4 purple");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2: invalid color purple"
+        );
     }
 }
@@ -1,198 +1,125 @@
-use std::cmp::max;
-
+use advent_of_code::grid::{Grid, Span};
+use advent_of_code::point::Point;
+use advent_of_code::template::visualize::FrameSink;
+use advent_of_code::template::{ANSI_BOLD, ANSI_RESET};
 use itertools::Itertools;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, visualize = visualize);
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum SchematicCell {
     Blank,
     Symbol(char),
-    Number(char),
+    Number(u32),
 }
 
-#[derive(Debug)]
-struct EngineSchematic {
-    schematic: Vec<Vec<SchematicCell>>,
+fn engine_parse(s: &str) -> Grid<SchematicCell> {
+    Grid::parse(s, |cell| match cell {
+        '.' => SchematicCell::Blank,
+        num if cell.is_ascii_digit() => {
+            SchematicCell::Number(num.to_digit(10).expect("Always a valid digit"))
+        }
+        symbol if cell.is_ascii_punctuation() => SchematicCell::Symbol(symbol),
+        other => panic!("'{other}' is not a valid Schematic Cell Char"),
+    })
+    .expect("Schematic is a rectangle")
 }
 
-fn engine_parse(s: &str) -> EngineSchematic {
-    let schematic = s
-        .lines()
-        .map(|line| {
-            line.chars()
-                .rev()
-                .map(|cell| match cell {
-                    '.' => SchematicCell::Blank,
-                    num if cell.is_ascii_digit() => SchematicCell::Number(num),
-                    symbol if cell.is_ascii_punctuation() => SchematicCell::Symbol(symbol),
-                    other => panic!("'{other}' is not a valid Schematic Cell Char"),
-                })
-                .collect_vec()
-        })
-        .collect_vec();
-
-    EngineSchematic { schematic }
+fn digit(cell: &SchematicCell) -> Option<u32> {
+    match cell {
+        SchematicCell::Number(digit) => Some(*digit),
+        _ => None,
+    }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let nearby_pattern: Vec<(isize, isize)> = vec![
-        (-1, 0),
-        (-1, -1),
-        (0, -1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-        (0, 1),
-        (-1, 1),
-    ];
+/// The numbers of the schematic, and whether each is next to a symbol.
+fn schematic_numbers(schematic: &Grid<SchematicCell>) -> Vec<(Span<u64>, bool)> {
+    schematic
+        .number_spans(digit)
+        .map(|number| {
+            let is_part = schematic
+                .span_neighbors(&number)
+                .any(|(_, cell)| matches!(cell, SchematicCell::Symbol(_)));
+            (number, is_part)
+        })
+        .collect()
+}
 
+pub fn part_one(input: &str) -> Option<u64> {
     let schematic = engine_parse(input);
 
-    let mut current_number = 0;
-    let mut current_digit_idx = 0;
-
-    let mut found_symbol_nearby = false;
+    Some(
+        schematic_numbers(&schematic)
+            .iter()
+            .filter(|(_, is_part)| *is_part)
+            .map(|(number, _)| number.value)
+            .sum(),
+    )
+}
 
-    let mut sum = 0;
+pub fn part_two(input: &str) -> Option<u64> {
+    let schematic = engine_parse(input);
+    let numbers = schematic.number_spans(digit).collect_vec();
 
-    for (line_idx, line) in schematic.schematic.iter().enumerate() {
-        for (cell_idx, cell) in line.iter().enumerate() {
-            match cell {
-                SchematicCell::Blank | SchematicCell::Symbol(_) => {
-                    if found_symbol_nearby {
-                        sum += current_number;
-                    }
-                    current_digit_idx = 0;
-                    current_number = 0;
-                    found_symbol_nearby = false;
-                }
-                SchematicCell::Number(num) => {
-                    // Try to see if there's a symbol nearby
-                    found_symbol_nearby = found_symbol_nearby
-                        || nearby_pattern
-                            .iter()
-                            .map(|pattern| {
-                                (
-                                    pattern.0.saturating_add(line_idx.try_into().unwrap()),
-                                    pattern.1.saturating_add(cell_idx.try_into().unwrap()),
-                                )
-                            })
-                            .any(|target| {
-                                schematic
-                                    .schematic
-                                    .get(TryInto::<usize>::try_into(max(target.0, 0)).unwrap())
-                                    .and_then(|target_line| {
-                                        target_line
-                                            .get(
-                                                TryInto::<usize>::try_into(max(target.1, 0))
-                                                    .unwrap(),
-                                            )
-                                            .map(|cell| matches!(cell, &SchematicCell::Symbol(_)))
-                                    })
-                                    .unwrap_or(false)
-                            });
-
-                    current_number += 10_u32.pow(current_digit_idx)
-                        * num.to_digit(10).expect("Always a valid digit");
-                    current_digit_idx += 1;
-                }
+    let total_sum = schematic
+        .iter()
+        .filter(|(_, cell)| **cell == SchematicCell::Symbol('*'))
+        .filter_map(|(position, _)| {
+            let gear = Point::from_index(position);
+
+            // a gear is next to exactly two part numbers.
+            match numbers
+                .iter()
+                .filter(|number| number.is_adjacent(gear))
+                .collect_vec()[..]
+            {
+                [first, second] => Some(first.value * second.value),
+                _ => None,
             }
-        }
-        // Reset the counters on a new line
-
-        if found_symbol_nearby {
-            sum += current_number;
-        }
-        current_digit_idx = 0;
-        current_number = 0;
-        found_symbol_nearby = false;
-    }
+        })
+        .sum();
 
-    Some(sum)
+    Some(total_sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+/// Scans the schematic line by line, highlighting the part numbers found so far.
+fn visualize(input: &str, frames: &mut FrameSink) {
     let schematic = engine_parse(input);
+    let numbers = schematic_numbers(&schematic);
 
-    let mut current_number: u32 = 0;
-    let mut current_digit_idx = 0;
+    for current in 0..schematic.height() {
+        let counted = numbers
+            .iter()
+            .filter(|(number, is_part)| *is_part && number.start.y <= current as i64)
+            .map(|(number, _)| number)
+            .collect_vec();
 
-    let mut current_number_indices: Vec<(u32, u32)> = Vec::new();
+        let mut frame = String::new();
 
-    let mut number_indices: Vec<(u32, Vec<(u32, u32)>)> = Vec::new();
+        for (line_idx, line) in schematic.rows().enumerate() {
+            frame.push_str(if line_idx == current { "> " } else { "  " });
 
-    schematic
-        .schematic
-        .iter()
-        .enumerate()
-        .for_each(|(line_idx, line)| {
-            line.iter().enumerate().for_each(|(cell_idx, cell)| {
-                match cell {
-                    SchematicCell::Blank | SchematicCell::Symbol(_) => {
-                        number_indices.push((current_number, current_number_indices.clone()));
+            for (cell_idx, cell) in line.iter().enumerate() {
+                let point = Point::from_index((cell_idx, line_idx));
 
-                        current_digit_idx = 0;
-                        current_number = 0;
-                        current_number_indices = Vec::new();
-                    }
-                    SchematicCell::Number(num) => {
-                        // Try to see if there's a symbol nearby
-                        current_number += 10_u32.pow(current_digit_idx)
-                            * num.to_digit(10).expect("Always a valid digit");
-                        current_digit_idx += 1;
-
-                        // Add to current_number_indices
-                        current_number_indices.push((line_idx as u32, cell_idx as u32));
+                match cell {
+                    SchematicCell::Blank => frame.push('.'),
+                    SchematicCell::Symbol(symbol) => frame.push(*symbol),
+                    SchematicCell::Number(num) if counted.iter().any(|x| x.contains(point)) => {
+                        frame.push_str(&format!("{ANSI_BOLD}{num}{ANSI_RESET}"));
                     }
+                    SchematicCell::Number(_) => frame.push('_'),
                 }
-            });
-        });
+            }
 
-    let mut total_sum = 0;
+            frame.push('\n');
+        }
 
-    // Now we find gear numbers
-    schematic
-        .schematic
-        .iter()
-        .enumerate()
-        .for_each(|(line_idx, line)| {
-            line.iter().enumerate().for_each(|(cell_idx, cell)| {
-                if let SchematicCell::Symbol('*') = cell {
-                    // We have a gear, lets try to find part numbers
-                    let nearby = number_indices
-                        .iter()
-                        .filter(|num| {
-                            // Check if near to this gear
-                            num.1.iter().any(|loc| {
-                                let dist = (loc.0 as isize - line_idx as isize).pow(2)
-                                    + (loc.1 as isize - cell_idx as isize).pow(2);
-
-                                if dist <= 2 {
-                                    // Nearby!
-                                    return true;
-                                }
-
-                                false
-                            })
-                        })
-                        .collect_vec();
-
-                    if nearby.len() == 2 {
-                        // We have a valid gear!
-
-                        let first = nearby.get(0).expect("We checked that this is valid").0;
-                        let second = nearby.get(1).expect("We checked that this is valid").0;
-
-                        let gear_ratio = first * second;
-
-                        total_sum += gear_ratio;
-                    }
-                }
-            });
-        });
+        let sum: u64 = counted.iter().map(|x| x.value).sum();
 
-    Some(total_sum)
+        frame.push_str(&format!("\nSum of part numbers: {sum}"));
+        frames.text(frame);
+    }
 }
 
 #[cfg(test)]
@@ -205,6 +132,17 @@ mod tests {
         assert_eq!(result, Some(4361));
     }
 
+    #[test]
+    fn test_schematic_numbers() {
+        let schematic = engine_parse(&advent_of_code::template::read_file("examples/part1", DAY));
+        let parts: u64 = schematic_numbers(&schematic)
+            .iter()
+            .filter(|(_, is_part)| *is_part)
+            .map(|(number, _)| number.value)
+            .sum();
+        assert_eq!(parts, 4361);
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
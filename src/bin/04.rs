@@ -1,61 +1,44 @@
 use std::collections::HashSet;
 
-use itertools::Itertools;
-use once_cell::sync::Lazy;
-use regex::Regex;
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::template::grammar::Grammar;
+use anyhow::Result;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, grammar = input_grammar());
 
-#[derive(Debug)]
-struct Card {
-    winning_nums: HashSet<u32>,
-    numbers_we_have: HashSet<u32>,
+fn input_grammar() -> Grammar {
+    Grammar::lines(r"Card +\d+:( +\d+)+ \|( +\d+)+", "Card N: ... | ...")
 }
 
-impl Card {
-    fn from_str(s: &str) -> Card {
-        static RE: Lazy<Regex> = Lazy::new(|| {
-            Regex::new(r"Card\s*(?P<card_num>\d+): (?P<winning_nums>(?:\s*\d+)+) \| (?P<our_nums>(?:\s*\d+)+)")
-                    .expect("Valid Regex")
-        });
-
-        let caps = RE.captures(s).expect("line Always matches regex");
-
-        Card {
-            winning_nums: HashSet::from_iter(
-                caps["winning_nums"]
-                    .split_ascii_whitespace()
-                    .map(|num| num.parse().expect("Valid u32 winning nums")),
-            ),
-
-            numbers_we_have: HashSet::from_iter(
-                caps["our_nums"]
-                    .split_ascii_whitespace()
-                    .map(|num| num.parse().expect("Valid u32 our nums")),
-            ),
-        }
+advent_of_code::from_line! {
+    #[line = r"Card\s*\d+: (?P<winning_nums>(?:\s*\d+)+) \| (?P<numbers_we_have>(?:\s*\d+)+)"]
+    #[derive(Debug)]
+    struct Card {
+        winning_nums: HashSet<u32>,
+        numbers_we_have: HashSet<u32>,
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(Card::from_str)
-            .map(|card| {
-                let winning_nums = card
-                    .numbers_we_have
-                    .intersection(&card.winning_nums)
-                    .count() as u32;
+fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse::lines(input).map(|line| line.parse()).collect()
+}
 
-                if winning_nums == 0 {
-                    0
-                } else {
-                    2_u32.pow(winning_nums - 1)
-                }
-            })
-            .sum(),
-    )
+pub fn part_one(input: &str) -> Result<u32> {
+    Ok(parse_cards(input)?
+        .iter()
+        .map(|card| {
+            let winning_nums = card
+                .numbers_we_have
+                .intersection(&card.winning_nums)
+                .count() as u32;
+
+            if winning_nums == 0 {
+                0
+            } else {
+                2_u32.pow(winning_nums - 1)
+            }
+        })
+        .sum())
 }
 
 fn process_scratch_card(cards: &Vec<Card>, scratch_card_to_process: usize) -> u32 {
@@ -83,14 +66,12 @@ fn process_scratch_card(cards: &Vec<Card>, scratch_card_to_process: usize) -> u3
     }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = input.lines().map(Card::from_str).collect_vec();
+pub fn part_two(input: &str) -> Result<u32> {
+    let cards = parse_cards(input)?;
 
-    Some(
-        (1..=cards.len())
-            .map(|card_idx| process_scratch_card(&cards, card_idx))
-            .sum(),
-    )
+    Ok((1..=cards.len())
+        .map(|card_idx| process_scratch_card(&cards, card_idx))
+        .sum())
 }
 
 #[cfg(test)]
@@ -98,14 +79,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY));
-        assert_eq!(result, Some(13));
+    fn test_part_one() -> Result<()> {
+        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY))?;
+        assert_eq!(result, 13);
+        Ok(())
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
-        assert_eq!(result, Some(30));
+    fn test_part_two() -> Result<()> {
+        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY))?;
+        assert_eq!(result, 30);
+        Ok(())
     }
 }
@@ -1,11 +1,27 @@
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::parse::{self, Line, ParseError};
+use advent_of_code::ranges::{OverlapError, RangeMap, RangeSet};
+use advent_of_code::template::grammar::{Grammar, Section};
+use anyhow::{anyhow, bail, Result};
 use itertools::Itertools;
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, grammar = input_grammar());
+
+fn input_grammar() -> Grammar {
+    Grammar::new()
+        .section(Section::new().line(r"seeds:( \d+)+", "seeds: N N ..."))
+        .repeated(
+            Section::new()
+                .line(r"\w+-to-\w+ map:", "<source>-to-<destination> map:")
+                .lines(r"\d+ \d+ \d+", "N N N"),
+        )
+}
 
 #[derive(Debug, Clone)]
 struct Mapping {
-    dest_start: u64,
-    source_start: u64,
-    range_len: u64,
+    dest_start: i64,
+    source_start: i64,
+    range_len: i64,
 }
 
 impl Mapping {
@@ -19,12 +35,12 @@ impl Mapping {
 }
 
 struct SeedRange {
-    start: u64,
-    length: u64,
+    start: i64,
+    length: i64,
 }
 
 impl SeedRange {
-    pub fn is_in_range(&self, value: u64) -> bool {
+    pub fn is_in_range(&self, value: i64) -> bool {
         self.start <= value && value < self.start + self.length
     }
 }
@@ -35,31 +51,19 @@ struct MapFunction {
 }
 
 impl Mapping {
-    pub fn from_line(s: &str) -> Mapping {
-        let mut parts = s.split_ascii_whitespace();
+    pub fn from_line(line: Line) -> Result<Mapping, ParseError> {
+        let [dest_start, source_start, range_len] = line.array()?;
 
-        Mapping {
-            dest_start: parts
-                .next()
-                .expect("always exists")
-                .parse()
-                .expect("Always u32"),
-            source_start: parts
-                .next()
-                .expect("always exists")
-                .parse()
-                .expect("Always u32"),
-            range_len: parts
-                .next()
-                .expect("always exists")
-                .parse()
-                .expect("Always u32"),
-        }
+        Ok(Mapping {
+            dest_start,
+            source_start,
+            range_len,
+        })
     }
 }
 
 impl MapFunction {
-    pub fn apply_function(&self, input: u64) -> u64 {
+    pub fn apply_function(&self, input: i64) -> i64 {
         let maybe_relevant_func = self.funcs.iter().find(|mapping| {
             mapping.source_start <= input && input < mapping.source_start + mapping.range_len
         });
@@ -78,93 +82,225 @@ impl MapFunction {
             funcs: self.funcs.into_iter().map(|f| f.reverse()).collect_vec(),
         }
     }
+
+    pub fn to_range_map(&self) -> Result<RangeMap, OverlapError> {
+        RangeMap::try_from_pieces(self.funcs.iter().map(|mapping| {
+            (
+                mapping.source_start..mapping.source_start + mapping.range_len,
+                mapping.dest_start - mapping.source_start,
+            )
+        }))
+    }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let initial_seeds = input
-        .lines()
-        .next()
-        .expect("always has a first line")
-        .split(": ")
-        .nth(1)
-        .expect("Always a num list part")
-        .split_ascii_whitespace()
-        .map(|s| s.parse::<u64>().expect("Every number is valid u32"))
-        .collect_vec();
+advent_of_code::from_line! {
+    #[line = r"(?P<source>\w+)-to-(?P<destination>\w+) map:"]
+    struct MapTitle {
+        source: String,
+        destination: String,
+    }
+}
 
-    let maps = input
-        .split("\n\n")
-        .skip(1) // Skip initial seed
-        .map(|map_func| {
-            let funcs = map_func
-                .lines()
-                .skip(1) // Skip title
-                .map(Mapping::from_line)
-                .collect_vec();
-            MapFunction { funcs }
-        })
-        .collect_vec();
+const SEED: &str = "seed";
+const LOCATION: &str = "location";
 
-    let final_ids = maps.iter().fold(initial_seeds, |ids, mapping_func| {
-        let ids = ids
-            .iter()
-            .map(|id| mapping_func.apply_function(*id))
+/// A map from one category to the next, e.g. `seed-to-soil`.
+#[derive(Debug, Clone)]
+struct CategoryMap {
+    destination: String,
+    function: MapFunction,
+}
+
+/// The seeds and the maps of the almanac, keyed by their source category.
+struct Almanac {
+    seeds: Vec<i64>,
+    maps: HashMap<String, CategoryMap>,
+}
+
+impl Almanac {
+    fn parse(input: &str) -> Result<Self> {
+        let mut blocks = parse::blocks(input);
+
+        let seeds = blocks
+            .next()
+            .and_then(|block| block.lines().next())
+            .ok_or_else(|| anyhow!("expected \"seeds: N N ...\""))?
+            .after("seeds")?
+            .unsigned()
+            .collect::<Result<_, _>>()?;
+
+        let mut maps = HashMap::new();
+
+        for block in blocks {
+            let mut lines = block.lines();
+            let title_line = lines.next().expect("blocks are never empty");
+            let title: MapTitle = title_line.parse()?;
+
+            if maps.contains_key(&title.source) {
+                return Err(title_line
+                    .error(format!("{} is mapped a second time", title.source))
+                    .into());
+            }
+
+            let funcs = lines.map(Mapping::from_line).collect::<Result<_, _>>()?;
+
+            maps.insert(
+                title.source,
+                CategoryMap {
+                    destination: title.destination,
+                    function: MapFunction { funcs },
+                },
+            );
+        }
+
+        let almanac = Self { seeds, maps };
+        almanac.check_chain()?;
+        Ok(almanac)
+    }
+
+    /// Checks that the maps form a single chain, which the puzzle implies but does not state.
+    fn check_chain(&self) -> Result<()> {
+        let destinations = self
+            .maps
+            .values()
+            .map(|map| map.destination.as_str())
+            .collect::<HashSet<_>>();
+
+        if destinations.len() < self.maps.len() {
+            bail!("two maps lead to the same category");
+        }
+
+        let starts = self
+            .maps
+            .keys()
+            .filter(|category| !destinations.contains(category.as_str()))
             .collect_vec();
-        ids
-    });
 
-    let lowest_location = final_ids
+        match starts[..] {
+            [start] if self.trace(start, 0).len() == self.maps.len() + 1 => Ok(()),
+            [_] => bail!("the maps contain a cycle"),
+            _ => bail!(
+                "the maps do not form a single chain, it would start at {}",
+                starts.iter().sorted().join(" and ")
+            ),
+        }
+    }
+
+    /// The maps that lead from category `from` to category `to`, in order.
+    fn stages(&self, from: &str, to: &str) -> Result<Vec<&MapFunction>> {
+        let mut stages = vec![];
+        let mut category = from;
+
+        while category != to {
+            let map = self
+                .maps
+                .get(category)
+                .ok_or_else(|| anyhow!("there is no path from {from} to {to}"))?;
+
+            stages.push(&map.function);
+            category = &map.destination;
+        }
+
+        Ok(stages)
+    }
+
+    /// A single map from category `from` to category `to`, e.g. from `soil` to `humidity`.
+    fn map_between(&self, from: &str, to: &str) -> Result<RangeMap> {
+        self.stages(from, to)?
+            .iter()
+            .try_fold(RangeMap::new(), |map, function| {
+                Ok(map.compose(&function.to_range_map()?))
+            })
+    }
+
+    /// The value in every category that follows `category`, starting with `value` itself.
+    /// Must only be called on a chain without cycles.
+    fn trace<'a>(&'a self, mut category: &'a str, mut value: i64) -> Vec<(&'a str, i64)> {
+        let mut trace = vec![(category, value)];
+
+        while let Some(map) = self.maps.get(category) {
+            value = map.function.apply_function(value);
+            category = &map.destination;
+            trace.push((category, value));
+
+            if trace.len() > self.maps.len() + 1 {
+                break;
+            }
+        }
+
+        trace
+    }
+}
+
+pub fn part_one(input: &str) -> Result<i64> {
+    let almanac = Almanac::parse(input)?;
+    let seed_to_location = almanac.map_between(SEED, LOCATION)?;
+
+    for seed in &almanac.seeds {
+        log::debug!(
+            "{}",
+            almanac
+                .trace(SEED, *seed)
+                .iter()
+                .map(|(category, value)| format!("{category} {value}"))
+                .join(", ")
+        );
+    }
+
+    almanac
+        .seeds
         .iter()
+        .map(|seed| seed_to_location.apply(*seed))
         .min()
-        .expect("Always at least one initial seed");
-
-    Some(*lowest_location)
+        .ok_or_else(|| anyhow!("no seeds"))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let initial_seeds = input
-        .lines()
-        .next()
-        .expect("always has a first line")
-        .split(": ")
-        .nth(1)
-        .expect("Always a num list part")
-        .split_ascii_whitespace()
+fn seed_ranges(seeds: &[i64]) -> Result<Vec<SeedRange>> {
+    seeds
         .chunks(2)
-        .into_iter()
-        .map(|mut s| {
-            let start = s
-                .next()
-                .expect("Always a start num")
-                .parse()
-                .expect("Always num");
-            let length: u64 = s
-                .next()
-                .expect("Always a length num")
-                .parse()
-                .expect("Always num");
-
-            SeedRange { start, length }
+        .map(|pair| match pair {
+            [start, length] => Ok(SeedRange {
+                start: *start,
+                length: *length,
+            }),
+            _ => Err(anyhow!("expected seeds as pairs of start and length")),
         })
-        .collect_vec();
+        .collect()
+}
 
-    let mut maps = input
-        .split("\n\n")
-        .skip(1) // Skip initial seed
-        .map(|map_func| {
-            let funcs = map_func
-                .lines()
-                .skip(1) // Skip title
-                .map(Mapping::from_line)
-                .collect_vec();
-            MapFunction { funcs }
-        })
-        .map(|map_func| map_func.reverse())
-        .collect_vec();
+/// Pushes the seed ranges through every map, splitting them where they cross mapping boundaries.
+pub fn part_two(input: &str) -> Result<i64> {
+    let almanac = Almanac::parse(input)?;
 
-    maps.reverse();
+    let seeds = seed_ranges(&almanac.seeds)?
+        .iter()
+        .map(|range| range.start..range.start + range.length)
+        .collect::<RangeSet>();
+
+    let locations = almanac
+        .stages(SEED, LOCATION)?
+        .iter()
+        .try_fold(seeds, |ids, mapping_func| {
+            Ok::<_, OverlapError>(mapping_func.to_range_map()?.apply_set(&ids))
+        })?;
 
-    let lowest_ending_that_exists = (0..u64::MAX)
+    locations.min().ok_or_else(|| anyhow!("no seeds"))
+}
+
+/// Walks locations upwards from 0 through the reversed maps until one leads back to a seed.
+/// Takes millions of steps on real inputs, kept to cross-check [`part_two`].
+pub fn part_two_brute_force(input: &str) -> Result<i64> {
+    let almanac = Almanac::parse(input)?;
+    let initial_seeds = seed_ranges(&almanac.seeds)?;
+
+    let maps = almanac
+        .stages(SEED, LOCATION)?
+        .into_iter()
+        .rev()
+        .map(|map_func| map_func.clone().reverse())
+        .collect_vec();
+
+    let lowest_ending_that_exists = (0..i64::MAX)
         .find(|ending_id| {
             let start_id = maps.iter().fold(*ending_id, |id, mapping_func| {
                 mapping_func.apply_function(id)
@@ -175,7 +311,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         })
         .expect("Always one valid num");
 
-    Some(lowest_ending_that_exists)
+    Ok(lowest_ending_that_exists)
 }
 
 #[cfg(test)]
@@ -183,14 +319,71 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY));
-        assert_eq!(result, Some(35));
+    fn test_part_one() -> Result<()> {
+        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY))?;
+        assert_eq!(result, 35);
+        Ok(())
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
-        assert_eq!(result, Some(46));
+    fn test_part_two() -> Result<()> {
+        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY))?;
+        assert_eq!(result, 46);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two_brute_force() -> Result<()> {
+        let input = advent_of_code::template::read_file("examples/part2", DAY);
+        assert_eq!(part_two_brute_force(&input)?, part_two(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_named_maps() -> Result<()> {
+        let almanac = Almanac::parse(&advent_of_code::template::read_file("examples/part1", DAY))?;
+
+        assert_eq!(almanac.map_between("soil", "humidity")?.apply(81), 78);
+        assert_eq!(
+            almanac.trace(SEED, 79),
+            vec![
+                ("seed", 79),
+                ("soil", 81),
+                ("fertilizer", 81),
+                ("water", 81),
+                ("light", 74),
+                ("temperature", 78),
+                ("humidity", 78),
+                ("location", 82)
+            ]
+        );
+        assert!(almanac.map_between("humidity", "soil").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_chain_is_checked() {
+        let forked = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nwater-to-soil map:\n1 2 3\n";
+        assert_eq!(
+            Almanac::parse(forked).err().map(|err| err.to_string()),
+            Some("two maps lead to the same category".to_string())
+        );
+
+        let twice = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nseed-to-water map:\n1 2 3\n";
+        assert_eq!(
+            Almanac::parse(twice).err().map(|err| err.to_string()),
+            Some("line 6: seed is mapped a second time".to_string())
+        );
+    }
+
+    #[test]
+    fn test_overlapping_mappings() {
+        let overlapping = "seeds: 1 2\n\nseed-to-location map:\n10 0 5\n20 3 5\n";
+
+        assert_eq!(
+            part_one(overlapping).err().map(|err| err.to_string()),
+            Some("3..8 overlaps 0..5".to_string())
+        );
+        assert!(part_two(overlapping).is_err());
     }
 }
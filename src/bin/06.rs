@@ -1,5 +1,5 @@
-use itertools::Itertools;
-use joinery::JoinableIterator;
+use advent_of_code::parse::{self, Line};
+use anyhow::{anyhow, Result};
 
 advent_of_code::solution!(6);
 
@@ -18,56 +18,33 @@ impl From<(u64, u64)> for Race {
     }
 }
 
-fn races_from_str(s: &str) -> Vec<Race> {
-    let mut parts = s.lines();
+/// The `Time:` and `Distance:` lines of the input.
+fn race_lines(s: &str) -> Result<(Line<'_>, Line<'_>)> {
+    let mut lines = parse::lines(s);
 
-    let times = parts
-        .next()
-        .expect("awlays a time line")
-        .split_ascii_whitespace()
-        .skip(1);
+    match (lines.next(), lines.next()) {
+        (Some(time), Some(distance)) => Ok((time.after("Time")?, distance.after("Distance")?)),
+        _ => Err(anyhow!("expected a time and a distance line")),
+    }
+}
 
-    let distances: std::iter::Skip<std::str::SplitAsciiWhitespace<'_>> = parts
-        .next()
-        .expect("awlays a dist line")
-        .split_ascii_whitespace()
-        .skip(1);
+fn races_from_str(s: &str) -> Result<Vec<Race>> {
+    let (times, distances) = race_lines(s)?;
 
     times
-        .zip(distances)
-        .map(|(time, dist)| {
-            (
-                time.parse().expect("Time always a number"),
-                dist.parse().expect("Dist always a number"),
-            )
-        })
-        .map(Into::<Race>::into)
-        .collect_vec()
+        .unsigned()
+        .zip(distances.unsigned())
+        .map(|(time, dist)| Ok((time?, dist?).into()))
+        .collect()
 }
 
-fn race_from_str_no_gaps(s: &str) -> Race {
-    let mut parts = s.lines();
-
-    let time = parts
-        .next()
-        .expect("awlays a time line")
-        .split_ascii_whitespace()
-        .skip(1)
-        .join_concat()
-        .to_string();
-
-    let distance = parts
-        .next()
-        .expect("awlays a dist line")
-        .split_ascii_whitespace()
-        .skip(1)
-        .join_concat()
-        .to_string();
-
-    Race {
-        time: time.parse().expect("Time always a number"),
-        distance: distance.parse().expect("Dist always a number"),
-    }
+fn race_from_str_no_gaps(s: &str) -> Result<Race> {
+    let (time, distance) = race_lines(s)?;
+
+    Ok(Race {
+        time: time.joined_digits()?,
+        distance: distance.joined_digits()?,
+    })
 }
 
 fn num_ways_to_beat_race(race: &Race) -> u64 {
@@ -77,16 +54,16 @@ fn num_ways_to_beat_race(race: &Race) -> u64 {
         .count() as u64
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let races = races_from_str(input);
+pub fn part_one(input: &str) -> Result<u64> {
+    let races = races_from_str(input)?;
 
-    Some(races.iter().map(num_ways_to_beat_race).product())
+    Ok(races.iter().map(num_ways_to_beat_race).product())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let race = race_from_str_no_gaps(input);
+pub fn part_two(input: &str) -> Result<u64> {
+    let race = race_from_str_no_gaps(input)?;
 
-    Some(num_ways_to_beat_race(&race))
+    Ok(num_ways_to_beat_race(&race))
 }
 
 #[cfg(test)]
@@ -94,14 +71,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY));
-        assert_eq!(result, Some(288));
+    fn test_part_one() -> Result<()> {
+        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY))?;
+        assert_eq!(result, 288);
+        Ok(())
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
-        assert_eq!(result, Some(71503));
+    fn test_part_two() -> Result<()> {
+        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY))?;
+        assert_eq!(result, 71503);
+        Ok(())
     }
 }
@@ -1,27 +1,51 @@
-use std::collections::HashMap;
-
+use advent_of_code::cycle::{self, Cycle};
+use advent_of_code::graph::{Graph, NodeId};
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use regex::Regex;
 
 advent_of_code::solution!(8);
 
-#[derive(Debug)]
-struct NodeDirection {
-    left: String,
-    right: String,
+advent_of_code::from_line! {
+    #[line = r"(?P<node>\w{3}) = \((?P<left>\w{3}), (?P<right>\w{3})\)"]
+    struct MapLine {
+        node: String,
+        left: String,
+        right: String,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Left,
+    Right,
 }
 
+/// The instructions, and the network with an edge per direction from every node.
 #[derive(Debug)]
 struct Map {
     directions: Vec<Direction>,
-    map: HashMap<String, NodeDirection>,
+    network: Graph<String, Direction>,
 }
 
-#[derive(Debug)]
-enum Direction {
-    Left,
-    Right,
+impl Map {
+    /// Follows the instructions from `start` until `is_end` holds, returns the number of steps.
+    fn steps(&self, start: NodeId, is_end: impl Fn(&str) -> bool) -> usize {
+        let mut current_node = start;
+        let mut directions = self.directions.iter().cycle();
+        let mut count = 0;
+
+        while !is_end(self.network.label(current_node)) {
+            let next_dir = directions.next().expect("Cycled Iter never ends");
+
+            current_node = self
+                .network
+                .follow(current_node, next_dir)
+                .expect("Node always exists");
+
+            count += 1;
+        }
+
+        count
+    }
 }
 
 const START: &str = "AAA";
@@ -43,93 +67,70 @@ fn file_to_node_map(s: &str) -> Map {
 
     let lines = lines.skip(1); // Empty Line
 
-    static LINE_MAP_RE: Lazy<Regex> = Lazy::new(|| {
-        Regex::new(r"(?P<node>\w{3}) = \((?P<left>\w{3}), (?P<right>\w{3})\)").expect("Valid Regex")
-    });
-    let map = lines
-        .map(|line| {
-            let matches = LINE_MAP_RE
-                .captures(line)
-                .expect("Every line contains a valid map line");
-
-            (
-                matches["node"].to_string(),
-                NodeDirection {
-                    left: matches["left"].to_string(),
-                    right: matches["right"].to_string(),
-                },
-            )
-        })
-        .collect();
+    let mut network = Graph::new();
+
+    for line in lines {
+        let line: MapLine = line.parse().expect("Every line contains a valid map line");
 
-    Map { directions, map }
+        network.add_edge(line.node.clone(), line.left, Direction::Left);
+        network.add_edge(line.node, line.right, Direction::Right);
+    }
+
+    Map {
+        directions,
+        network,
+    }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let map = file_to_node_map(input);
+    let start = map.network.id(START)?;
 
-    let mut current_node = START;
-    let mut directions = map.directions.iter().cycle();
-
-    let mut count = 0;
-
-    while current_node != END {
-        let next_dir = directions.next().expect("Cycled Iter never ends");
-
-        let potential_directions = map.map.get(current_node).expect("Node always exists");
-
-        match next_dir {
-            Direction::Left => current_node = &potential_directions.left,
-            Direction::Right => current_node = &potential_directions.right,
-        }
-
-        count += 1;
-    }
+    u32::try_from(map.steps(start, |node| node == END)).ok()
+}
 
-    Some(count)
+/// The shape of a ghost's walk from `start`, hit whenever it stands on a node ending in `Z`.
+/// The state includes the position in the instructions, the same node is a different state for every instruction.
+fn ghost_cycle(map: &Map, start: NodeId) -> Cycle {
+    Cycle::find(
+        (start, 0),
+        |(node, instruction)| {
+            let next_node = map
+                .network
+                .follow(*node, &map.directions[*instruction])
+                .expect("Node always exists");
+
+            (next_node, (instruction + 1) % map.directions.len())
+        },
+        |(node, _)| map.network.label(*node).ends_with('Z'),
+    )
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Option<u64> {
     let map = file_to_node_map(input);
 
-    let current_nodes = map
-        .map
-        .keys()
-        .filter(|node| node.ends_with('A'))
-        .cloned()
-        .collect_vec();
-
-    // Key insight: All paths are cycles.
+    let (starts, ghosts): (Vec<_>, Vec<_>) = map
+        .network
+        .nodes()
+        .filter(|node| map.network.label(*node).ends_with('A'))
+        .map(|node| (map.network.label(node).as_str(), ghost_cycle(&map, node)))
+        .unzip();
 
-    let path_cycle_lengths = current_nodes
+    let irregular = starts
         .iter()
-        .map(|node| {
-            let mut current_node = node;
-            let mut directions = map.directions.iter().cycle();
-            let mut count: usize = 0;
-
-            while !current_node.ends_with('Z') {
-                let next_dir = directions.next().expect("Cycled Iter never ends");
-
-                let potential_directions = map.map.get(current_node).expect("Node always exists");
-
-                match next_dir {
-                    Direction::Left => current_node = &potential_directions.left,
-                    Direction::Right => current_node = &potential_directions.right,
-                }
-
-                count += 1;
-            }
-
-            count
-        })
-        .collect_vec();
+        .zip(&ghosts)
+        .filter(|(_, ghost)| !ghost.lcm_shortcut_holds())
+        .map(|(start, _)| start)
+        .join(", ");
+
+    if !irregular.is_empty() {
+        log::warn!(
+            "the ghosts from {irregular} are not hit at multiples of their first hit, \
+             the LCM of the first hits is not the answer"
+        );
+    }
 
-    Some(
-        path_cycle_lengths
-            .iter()
-            .fold(1usize, |acc, len| num::integer::lcm(acc, *len)),
-    )
+    cycle::first_common_hit(&ghosts)
 }
 
 //11678319315857
@@ -149,4 +150,19 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_lcm_shortcut() {
+        let map = file_to_node_map(&advent_of_code::template::read_file("examples/part2", DAY));
+        let ghosts = ["11A", "22A"].map(|start| ghost_cycle(&map, map.network.id(start).unwrap()));
+
+        assert!(ghosts.iter().all(Cycle::lcm_shortcut_holds));
+        assert_eq!(
+            ghosts
+                .iter()
+                .map(|ghost| ghost.first_hit().unwrap())
+                .fold(1, num::integer::lcm),
+            6
+        );
+    }
 }
@@ -1,16 +1,13 @@
+use advent_of_code::parse::{self, ParseError};
+use anyhow::Result;
 use itertools::Itertools;
 
 advent_of_code::solution!(9);
 
-fn time_data_from_file(s: &str) -> Vec<Vec<i64>> {
-    s.trim()
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|num| num.parse().expect("Always a num"))
-                .collect_vec()
-        })
-        .collect_vec()
+fn time_data_from_file(s: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    parse::lines(s.trim())
+        .map(|line| line.signed().collect())
+        .collect()
 }
 
 fn extrapolate(data: &[i64]) -> i64 {
@@ -56,21 +53,21 @@ fn extrapolate(data: &[i64]) -> i64 {
     *current_data.last().expect("Always at least one datapoint")
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let data = time_data_from_file(input);
+pub fn part_one(input: &str) -> Result<i64> {
+    let data = time_data_from_file(input)?;
 
-    Some(data.iter().map(|v| extrapolate(v)).sum())
+    Ok(data.iter().map(|v| extrapolate(v)).sum())
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let mut data = time_data_from_file(input);
+pub fn part_two(input: &str) -> Result<i64> {
+    let mut data = time_data_from_file(input)?;
 
     // We need to back-extrapolate, so lets reverse all the sequences
     for vec in &mut data {
         vec.reverse();
     }
 
-    Some(data.iter().map(|v| extrapolate(v)).sum())
+    Ok(data.iter().map(|v| extrapolate(v)).sum())
 }
 
 #[cfg(test)]
@@ -78,14 +75,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY));
-        assert_eq!(result, Some(114));
+    fn test_part_one() -> Result<()> {
+        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY))?;
+        assert_eq!(result, 114);
+        Ok(())
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
-        assert_eq!(result, None);
+    fn test_part_two() -> Result<()> {
+        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY))?;
+        assert_eq!(result, 2);
+        Ok(())
     }
 }
@@ -0,0 +1,278 @@
+/// Cycle detection for deterministic sequences, and the first step at which several sequences are hit together.
+/// Every sequence `start, step(start), step(step(start)), ...` of a finite state space runs through a tail into a cycle,
+/// so its hits are the hits of the tail plus the hits of the cycle, repeated forever.
+use num::integer::gcd;
+
+/// Finds the cycle of `start, step(start), ...` with Brent's algorithm, returns `(tail, length)`:
+/// the number of steps before the sequence enters its cycle, and the length of the cycle.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> (u64, u64) {
+    // find the length by letting the hare run ahead in doubling stretches, the tortoise
+    // teleports to the hare at the start of every stretch.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the first state of the cycle.
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        tail += 1;
+    }
+
+    (tail, length)
+}
+
+/// The shape of a sequence, and the steps at which a predicate holds on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    tail: u64,
+    length: u64,
+    /// The hits in `0..tail + length`, in ascending order.
+    hits: Vec<u64>,
+}
+
+impl Cycle {
+    /// Runs through the tail and one round of the cycle of `start, step(start), ...`, recording where `is_hit` holds.
+    pub fn find<S: Clone + Eq>(
+        start: S,
+        mut step: impl FnMut(&S) -> S,
+        mut is_hit: impl FnMut(&S) -> bool,
+    ) -> Self {
+        let (tail, length) = brent(start.clone(), &mut step);
+
+        let mut hits = vec![];
+        let mut state = start;
+        for index in 0..tail + length {
+            if is_hit(&state) {
+                hits.push(index);
+            }
+            state = step(&state);
+        }
+
+        Self { tail, length, hits }
+    }
+
+    /// The number of steps before the sequence enters its cycle.
+    #[must_use]
+    pub fn tail(&self) -> u64 {
+        self.tail
+    }
+
+    #[must_use]
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// The hits in the tail, and in the first round of the cycle.
+    #[must_use]
+    pub fn hits(&self) -> &[u64] {
+        &self.hits
+    }
+
+    /// The hits in the first round of the cycle, they repeat every [`Cycle::length`] steps.
+    #[must_use]
+    pub fn cycle_hits(&self) -> &[u64] {
+        &self.hits[self.hits.partition_point(|hit| *hit < self.tail)..]
+    }
+
+    #[must_use]
+    pub fn first_hit(&self) -> Option<u64> {
+        self.hits.first().copied()
+    }
+
+    #[must_use]
+    pub fn is_hit(&self, index: u64) -> bool {
+        let index = if index < self.tail {
+            index
+        } else {
+            self.tail + (index - self.tail) % self.length
+        };
+
+        self.hits.binary_search(&index).is_ok()
+    }
+
+    /// Whether the hits are exactly the positive multiples of the first hit.
+    /// Only then is the LCM of the first hits of several sequences the first step they are hit together.
+    #[must_use]
+    pub fn lcm_shortcut_holds(&self) -> bool {
+        let Some(first) = self.first_hit().filter(|first| *first > 0) else {
+            return false;
+        };
+
+        // both sets repeat every `length` steps from the tail on, so one round after step 0 decides.
+        self.length.is_multiple_of(first)
+            && (1..=self.tail + self.length)
+                .all(|index| self.is_hit(index) == index.is_multiple_of(first))
+    }
+}
+
+/// The first step at which all `cycles` are hit, [`None`] if that never happens or does not fit into an `u64`.
+#[must_use]
+pub fn first_common_hit(cycles: &[Cycle]) -> Option<u64> {
+    let longest_tail = cycles.iter().max_by_key(|cycle| cycle.tail)?;
+    let start = longest_tail.tail;
+
+    // until every sequence is in its cycle, the hits in the longest tail are the only candidates.
+    if let Some(hit) = longest_tail
+        .hits
+        .iter()
+        .take_while(|hit| **hit < start)
+        .find(|hit| cycles.iter().all(|cycle| cycle.is_hit(**hit)))
+    {
+        return Some(*hit);
+    }
+
+    // afterwards every combination of hit offsets is a system of congruences.
+    let mut systems = vec![(0, 1)];
+
+    for cycle in cycles {
+        systems = systems
+            .iter()
+            .flat_map(|system| {
+                cycle
+                    .cycle_hits()
+                    .iter()
+                    .filter_map(move |hit| crt(&[*system, (hit % cycle.length, cycle.length)]))
+            })
+            .collect();
+    }
+
+    systems
+        .into_iter()
+        .filter_map(|(residue, modulus)| {
+            // the smallest number from `start` on that is congruent to `residue`.
+            if residue >= start {
+                Some(residue)
+            } else {
+                let rounds = (start - residue).div_ceil(modulus);
+                residue.checked_add(rounds.checked_mul(modulus)?)
+            }
+        })
+        .min()
+}
+
+/// Solves `x ≡ residue (mod modulus)` for all pairs, where the moduli do not need to be coprime.
+/// Returns the smallest solution with the combined modulus, [`None`] if the congruences contradict
+/// each other or the combined modulus does not fit into an `u64`.
+#[must_use]
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences.iter().try_fold(
+        (0, 1),
+        |(residue, modulus), (next_residue, next_modulus)| {
+            let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+            let next_modulus = i128::from(*next_modulus);
+            let next_residue = i128::from(*next_residue) % next_modulus;
+
+            let divisor = gcd(modulus, next_modulus);
+            let difference = next_residue - residue;
+            if difference % divisor != 0 {
+                return None;
+            }
+
+            // residue + modulus * k ≡ next_residue (mod next_modulus)
+            let (_, inverse, _) = extended_gcd(modulus / divisor, next_modulus / divisor);
+            let step = next_modulus / divisor;
+            let k = (difference / divisor * inverse).rem_euclid(step);
+            let combined = modulus * step;
+
+            Some((
+                u64::try_from((residue + modulus * k).rem_euclid(combined)).ok()?,
+                u64::try_from(combined).ok()?,
+            ))
+        },
+    )
+}
+
+/// Returns `(gcd, x, y)` with `a * x + b * y = gcd`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (divisor, x, y) = extended_gcd(b, a % b);
+        (divisor, y, x - a / b * y)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, crt, first_common_hit, Cycle};
+
+    /// `0, 1, 2, 3, 4, 5, 3, 4, 5, ...`
+    fn rho(state: &u64) -> u64 {
+        if *state == 5 {
+            3
+        } else {
+            state + 1
+        }
+    }
+
+    fn counter(modulus: u64) -> impl Fn(&u64) -> u64 {
+        move |state| (state + 1) % modulus
+    }
+
+    #[test]
+    fn finds_cycles() {
+        assert_eq!(brent(0, rho), (3, 3));
+        assert_eq!(brent(0, counter(7)), (0, 7));
+        assert_eq!(brent(5, |_| 5), (0, 1));
+
+        let cycle = Cycle::find(0, rho, |state| *state == 1 || *state == 4);
+        assert_eq!(cycle.hits(), [1, 4]);
+        assert_eq!(cycle.cycle_hits(), [4]);
+        assert!(cycle.is_hit(1) && cycle.is_hit(7) && cycle.is_hit(301));
+        assert!(!cycle.is_hit(300));
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn finds_common_hits() {
+        // hit at 1, 5, 9, ... and at 3, 9, 15, ...
+        let fours = Cycle::find(0, counter(4), |state| *state == 1);
+        let sixes = Cycle::find(0, counter(6), |state| *state == 3);
+        assert!(!fours.lcm_shortcut_holds() && !sixes.lcm_shortcut_holds());
+        assert_eq!(first_common_hit(&[fours.clone(), sixes]), Some(9));
+
+        // both only ever hit in the tail.
+        let once = Cycle::find(0, rho, |state| *state == 2);
+        assert_eq!(first_common_hit(&[once.clone(), fours.clone()]), None);
+        assert_eq!(first_common_hit(&[once.clone(), once]), Some(2));
+
+        // a tail of one step into a cycle of three that is hit at its end, like the day 8 ghosts.
+        let ghost = Cycle::find(
+            100,
+            |state| if *state == 100 { 1 } else { state % 3 + 1 },
+            |state| *state == 3,
+        );
+        assert_eq!(
+            (ghost.tail(), ghost.length(), ghost.hits()),
+            (1, 3, &[3][..])
+        );
+        assert!(ghost.lcm_shortcut_holds());
+        assert_eq!(first_common_hit(&[ghost, fours]), Some(9));
+    }
+}
@@ -114,6 +114,109 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days of advent, e.g. parsed from a list of days and ranges like `1-9,12`.
+///
+/// ```
+/// # use advent_of_code::{Day, DaySet};
+/// let days: DaySet = "1-3,12".parse().unwrap();
+/// assert!(days.contains(Day::new(2).unwrap()));
+/// assert_eq!(days.iter().map(|day| day.into_inner()).collect::<Vec<_>>(), vec![1, 2, 3, 12]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates an empty [`DaySet`].
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates a [`DaySet`] containing every day of advent.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.0);
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// An iterator over the days in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.contains(*day))
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::new();
+
+        for item in s.split(',').map(str::trim) {
+            let err = || DaySetFromStrError(item.to_string());
+
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (
+                    start.trim().parse::<Day>().map_err(|_| err())?,
+                    end.trim().parse::<Day>().map_err(|_| err())?,
+                ),
+                None => {
+                    let day = item.parse::<Day>().map_err(|_| err())?;
+                    (day, day)
+                }
+            };
+
+            if start > end {
+                return Err(err());
+            }
+
+            all_days()
+                .filter(|day| start <= *day && *day <= end)
+                .for_each(|day| set.insert(day));
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day or range `{}`, expecting a list like `1-9,12` of days between 1 and 25",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +237,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +270,28 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn day_set_from_str() {
+        let days: DaySet = "1-3, 12,20-21".parse().unwrap();
+        let days = days.iter().collect::<Vec<_>>();
+        assert_eq!(
+            days,
+            vec![Day(1), Day(2), Day(3), Day(12), Day(20), Day(21)]
+        );
+
+        let days: DaySet = "1-25".parse().unwrap();
+        assert_eq!(days, DaySet::all());
+    }
+
+    #[test]
+    fn day_set_from_str_invalid() {
+        assert!("".parse::<DaySet>().is_err());
+        assert!("0-3".parse::<DaySet>().is_err());
+        assert!("5-2".parse::<DaySet>().is_err());
+        assert!("1,26".parse::<DaySet>().is_err());
+        assert!("1-".parse::<DaySet>().is_err());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
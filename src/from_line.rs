@@ -0,0 +1,219 @@
+/// Declares structs that are read from a line of input by a regex with named captures, see [`from_line!`](crate::from_line!).
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{self, Display};
+use std::hash::Hash;
+
+use regex::Captures;
+
+#[doc(hidden)]
+pub use once_cell::sync::Lazy;
+#[doc(hidden)]
+pub use regex::Regex;
+
+/// Generates a struct and a [`FromStr`](std::str::FromStr) implementation that fills every field from the capture group of the same name.
+/// The regex must match the whole line and is compiled once, on first use. Fields are converted with [`FromCapture`], collections read whitespace separated items:
+///
+/// ```
+/// advent_of_code::from_line! {
+///     #[line = r"Card +\d+: (?P<winning>[\d ]+) \| (?P<numbers>[\d ]+)"]
+///     #[derive(Debug)]
+///     struct Card {
+///         winning: Vec<u32>,
+///         numbers: std::collections::HashSet<u32>,
+///     }
+/// }
+///
+/// let card: Card = "Card 1: 41 48 | 83 86  6".parse().unwrap();
+/// assert_eq!(card.winning, vec![41, 48]);
+/// ```
+#[macro_export]
+macro_rules! from_line {
+    (
+        #[line = $pattern:literal]
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $ty,)*
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::from_line::FromLineError;
+
+            fn from_str(line: &str) -> Result<Self, Self::Err> {
+                use $crate::from_line::{Lazy, Regex};
+
+                static RE: Lazy<Regex> = Lazy::new(|| {
+                    Regex::new(concat!("^(?:", $pattern, ")$"))
+                        .expect(concat!("invalid regex for ", stringify!($name)))
+                });
+
+                let captures = RE.captures(line).ok_or_else(|| {
+                    $crate::from_line::FromLineError::NoMatch {
+                        pattern: $pattern,
+                        line: line.to_string(),
+                    }
+                })?;
+
+                Ok(Self {
+                    $($field: $crate::from_line::field(&captures, stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+/// A type that can be read from the text of a capture group.
+pub trait FromCapture: Sized {
+    fn from_capture(text: &str) -> Result<Self, String>;
+
+    /// The value of a group that did not participate in the match, [`None`] if the group is required.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_from_capture {
+    ($($ty:ty),*) => {
+        $(
+            impl FromCapture for $ty {
+                fn from_capture(text: &str) -> Result<Self, String> {
+                    text.trim().parse().map_err(|err| format!("{err}"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_capture!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char
+);
+
+impl FromCapture for String {
+    fn from_capture(text: &str) -> Result<Self, String> {
+        Ok(text.to_string())
+    }
+}
+
+impl<T: FromCapture> FromCapture for Vec<T> {
+    fn from_capture(text: &str) -> Result<Self, String> {
+        text.split_whitespace().map(T::from_capture).collect()
+    }
+}
+
+impl<T: FromCapture + Eq + Hash> FromCapture for HashSet<T> {
+    fn from_capture(text: &str) -> Result<Self, String> {
+        text.split_whitespace().map(T::from_capture).collect()
+    }
+}
+
+impl<T: FromCapture + Ord> FromCapture for BTreeSet<T> {
+    fn from_capture(text: &str) -> Result<Self, String> {
+        text.split_whitespace().map(T::from_capture).collect()
+    }
+}
+
+/// An optional capture group, [`None`] if the group did not participate in the match.
+impl<T: FromCapture> FromCapture for Option<T> {
+    fn from_capture(text: &str) -> Result<Self, String> {
+        T::from_capture(text).map(Some)
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromLineError {
+    NoMatch {
+        pattern: &'static str,
+        line: String,
+    },
+    /// The regex has no group for a field, or the group did not match.
+    MissingGroup(&'static str),
+    Field {
+        name: &'static str,
+        value: String,
+        message: String,
+    },
+}
+
+impl std::error::Error for FromLineError {}
+
+impl Display for FromLineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoMatch { pattern, line } => {
+                write!(f, "\"{line}\" does not match /{pattern}/")
+            }
+            Self::MissingGroup(name) => write!(f, "no capture for field {name}"),
+            Self::Field {
+                name,
+                value,
+                message,
+            } => write!(f, "field {name}: can not read \"{value}\": {message}"),
+        }
+    }
+}
+
+/// Reads a field from its capture group, used by [`from_line!`](crate::from_line!).
+#[doc(hidden)]
+pub fn field<T: FromCapture>(captures: &Captures, name: &'static str) -> Result<T, FromLineError> {
+    let Some(capture) = captures.name(name) else {
+        return T::from_missing().ok_or(FromLineError::MissingGroup(name));
+    };
+
+    T::from_capture(capture.as_str()).map_err(|message| FromLineError::Field {
+        name,
+        value: capture.as_str().to_string(),
+        message,
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::FromLineError;
+
+    crate::from_line! {
+        #[line = r"(?P<name>\w+) (?P<count>-?\d+)(?: \[(?P<tags>[a-z ]+)\])?"]
+        #[derive(Debug, PartialEq)]
+        struct Entry {
+            name: String,
+            count: i32,
+            tags: Option<HashSet<String>>,
+        }
+    }
+
+    #[test]
+    fn reads_fields() {
+        assert_eq!(
+            "abc -12 [x y]".parse(),
+            Ok(Entry {
+                name: "abc".into(),
+                count: -12,
+                tags: Some(HashSet::from(["x".into(), "y".into()])),
+            })
+        );
+        assert_eq!("abc 3".parse::<Entry>().unwrap().tags, None);
+    }
+
+    #[test]
+    fn reports_errors() {
+        for line in ["abc", "abc 3 junk", "> abc 3"] {
+            assert!(
+                matches!(line.parse::<Entry>(), Err(FromLineError::NoMatch { .. })),
+                "{line}"
+            );
+        }
+        assert_eq!(
+            "abc 99999999999".parse::<Entry>().unwrap_err().to_string(),
+            "field count: can not read \"99999999999\": number too large to fit in target type"
+        );
+    }
+}
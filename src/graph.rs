@@ -0,0 +1,477 @@
+/// Directed graphs whose nodes are labelled by any hashable value and stored under compact integer ids.
+/// Labels are interned once while building the graph, searches then work on ids and plain vectors only.
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// The id of a node, valid for the graph that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// The position of the node in insertion order, usable as an index into per-node vectors.
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A directed graph with labels `L` on the nodes and labels `E` on the edges.
+#[derive(Debug, Clone)]
+pub struct Graph<L, E = ()> {
+    labels: Vec<L>,
+    ids: HashMap<L, NodeId>,
+    edges: Vec<Vec<(NodeId, E)>>,
+}
+
+impl<L, E> Default for Graph<L, E> {
+    fn default() -> Self {
+        Self {
+            labels: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<L: Clone + Eq + Hash, E> Graph<L, E> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the node labelled `label`, adding the node if it does not exist yet.
+    ///
+    /// # Panics
+    /// If the graph already holds `u32::MAX` nodes.
+    pub fn intern(&mut self, label: L) -> NodeId {
+        if let Some(id) = self.ids.get(&label) {
+            return *id;
+        }
+
+        let id = NodeId(u32::try_from(self.labels.len()).expect("fewer than 2^32 nodes"));
+        self.labels.push(label.clone());
+        self.ids.insert(label, id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// Adds an edge between two labels, adding their nodes if needed.
+    pub fn add_edge(&mut self, from: L, to: L, edge: E) -> (NodeId, NodeId) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        self.connect(from, to, edge);
+        (from, to)
+    }
+
+    #[must_use]
+    pub fn id<Q>(&self, label: &Q) -> Option<NodeId>
+    where
+        L: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+}
+
+impl<L, E> Graph<L, E> {
+    /// Adds an edge between two existing nodes, parallel edges are allowed.
+    pub fn connect(&mut self, from: NodeId, to: NodeId, edge: E) {
+        self.edges[from.index()].push((to, edge));
+    }
+
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &L {
+        &self.labels[id.index()]
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// All node ids in insertion order.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        // ids are only created by `intern`, so they always fit into an u32.
+        (0..self.labels.len() as u32).map(NodeId)
+    }
+
+    /// The outgoing edges of `id` with their labels, in insertion order.
+    #[must_use]
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, E)] {
+        &self.edges[id.index()]
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges(id).iter().map(|(to, _)| *to)
+    }
+
+    /// The target of the first edge of `id` labelled `edge`.
+    #[must_use]
+    pub fn follow(&self, id: NodeId, edge: &E) -> Option<NodeId>
+    where
+        E: PartialEq,
+    {
+        self.edges(id)
+            .iter()
+            .find(|(_, label)| label == edge)
+            .map(|(to, _)| *to)
+    }
+
+    /// Shortest paths from `start` counted in edges.
+    #[must_use]
+    pub fn bfs(&self, start: NodeId) -> Paths {
+        let mut paths = Paths::new(self.len(), start);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            let cost = paths.costs[node.index()].expect("queued nodes are reached");
+
+            for next in self.neighbors(node) {
+                if paths.costs[next.index()].is_none() {
+                    paths.reach(next, node, cost + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// The nodes reachable from `start` in depth-first pre-order, following edges in insertion order.
+    #[must_use]
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if std::mem::replace(&mut seen[node.index()], true) {
+                continue;
+            }
+
+            order.push(node);
+            // reversed, so the first edge is popped first.
+            stack.extend(
+                self.neighbors(node)
+                    .filter(|next| !seen[next.index()])
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev(),
+            );
+        }
+
+        order
+    }
+
+    /// Cheapest paths from `start`, where `cost` gives the non-negative cost of every edge.
+    #[must_use]
+    pub fn dijkstra(&self, start: NodeId, cost: impl Fn(&E) -> u64) -> Paths {
+        let mut paths = Paths::new(self.len(), start);
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((total, node))) = heap.pop() {
+            if paths.costs[node.index()].is_some_and(|best| best < total) {
+                continue;
+            }
+
+            for (next, edge) in self.edges(node) {
+                let next_total = total + cost(edge);
+
+                if paths.costs[next.index()].is_none_or(|best| next_total < best) {
+                    paths.reach(*next, node, next_total);
+                    heap.push(Reverse((next_total, *next)));
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// The cheapest path from `start` to `goal` with its cost.
+    /// `heuristic` must never overestimate the remaining cost to `goal`, or the path may not be the cheapest.
+    #[must_use]
+    pub fn astar(
+        &self,
+        start: NodeId,
+        goal: NodeId,
+        cost: impl Fn(&E) -> u64,
+        heuristic: impl Fn(NodeId) -> u64,
+    ) -> Option<(u64, Vec<NodeId>)> {
+        let mut paths = Paths::new(self.len(), start);
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+
+        while let Some(Reverse((_, total, node))) = heap.pop() {
+            if node == goal {
+                return Some((total, paths.path_to(goal)?));
+            }
+            if paths.costs[node.index()].is_some_and(|best| best < total) {
+                continue;
+            }
+
+            for (next, edge) in self.edges(node) {
+                let next_total = total + cost(edge);
+
+                if paths.costs[next.index()].is_none_or(|best| next_total < best) {
+                    paths.reach(*next, node, next_total);
+                    heap.push(Reverse((next_total + heuristic(*next), next_total, *next)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The nodes ordered so that every edge points forwards, or [`None`] if the graph has a cycle.
+    #[must_use]
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0usize; self.len()];
+        for node in self.nodes() {
+            for next in self.neighbors(node) {
+                incoming[next.index()] += 1;
+            }
+        }
+
+        let mut ready = self
+            .nodes()
+            .filter(|node| incoming[node.index()] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+
+            for next in self.neighbors(node) {
+                incoming[next.index()] -= 1;
+                if incoming[next.index()] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// The strongly connected components, ordered so that edges between components only point forwards.
+    #[must_use]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        // Kosaraju: order the nodes by the time their search finished, then search the reversed
+        // graph in reverse finishing order, every search collects one component.
+        let mut finished = Vec::with_capacity(self.len());
+        let mut seen = vec![false; self.len()];
+
+        for root in self.nodes() {
+            if std::mem::replace(&mut seen[root.index()], true) {
+                continue;
+            }
+
+            // every node remembers how many of its edges have been followed.
+            let mut stack = vec![(root, 0)];
+
+            while let Some((node, edge)) = stack.last_mut() {
+                if let Some((next, _)) = self.edges(*node).get(*edge) {
+                    *edge += 1;
+                    if !std::mem::replace(&mut seen[next.index()], true) {
+                        stack.push((*next, 0));
+                    }
+                } else {
+                    finished.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut reversed = vec![vec![]; self.len()];
+        for node in self.nodes() {
+            for next in self.neighbors(node) {
+                reversed[next.index()].push(node);
+            }
+        }
+
+        let mut assigned = vec![false; self.len()];
+        let mut components = vec![];
+
+        for root in finished.into_iter().rev() {
+            if std::mem::replace(&mut assigned[root.index()], true) {
+                continue;
+            }
+
+            let mut component = vec![];
+            let mut stack = vec![root];
+
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for previous in &reversed[node.index()] {
+                    if !std::mem::replace(&mut assigned[previous.index()], true) {
+                        stack.push(*previous);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/// The result of a search from a single start node: the cost of reaching every node and the
+/// node it was reached from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    start: NodeId,
+    costs: Vec<Option<u64>>,
+    parents: Vec<Option<NodeId>>,
+}
+
+impl Paths {
+    fn new(len: usize, start: NodeId) -> Self {
+        let mut costs = vec![None; len];
+        costs[start.index()] = Some(0);
+
+        Self {
+            start,
+            costs,
+            parents: vec![None; len],
+        }
+    }
+
+    fn reach(&mut self, node: NodeId, parent: NodeId, cost: u64) {
+        self.costs[node.index()] = Some(cost);
+        self.parents[node.index()] = Some(parent);
+    }
+
+    #[must_use]
+    pub fn start(&self) -> NodeId {
+        self.start
+    }
+
+    /// The cost of the best path to `node`, [`None`] if it can not be reached.
+    #[must_use]
+    pub fn cost(&self, node: NodeId) -> Option<u64> {
+        self.costs[node.index()]
+    }
+
+    /// The node before `node` on the best path.
+    #[must_use]
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.parents[node.index()]
+    }
+
+    /// The best path from the start to `node`, including both ends.
+    #[must_use]
+    pub fn path_to(&self, node: NodeId) -> Option<Vec<NodeId>> {
+        self.cost(node)?;
+
+        let mut path = vec![node];
+        while let Some(parent) = self.parent(*path.last().expect("path is never empty")) {
+            path.push(parent);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Graph, NodeId};
+
+    /// `a -> b -> d`, `a -> c -> d` and a cycle `d -> e -> d`, costs on the edges.
+    fn example() -> Graph<&'static str, u64> {
+        let mut graph = Graph::new();
+        for (from, to, cost) in [
+            ("a", "b", 1),
+            ("b", "d", 5),
+            ("a", "c", 2),
+            ("c", "d", 1),
+            ("d", "e", 1),
+            ("e", "d", 1),
+        ] {
+            graph.add_edge(from, to, cost);
+        }
+        graph
+    }
+
+    fn labels(graph: &Graph<&'static str, u64>, ids: &[NodeId]) -> Vec<&'static str> {
+        ids.iter().map(|id| *graph.label(*id)).collect()
+    }
+
+    #[test]
+    fn interns_labels() {
+        let mut graph = example();
+        let a = graph.id("a").unwrap();
+
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.intern("a"), a);
+        assert_eq!(graph.id("z"), None);
+        assert_eq!(graph.follow(a, &2), graph.id("c"));
+        assert_eq!(
+            labels(&graph, &graph.neighbors(a).collect::<Vec<_>>()),
+            ["b", "c"]
+        );
+    }
+
+    #[test]
+    fn searches() {
+        let graph = example();
+        let (a, d, e) = (
+            graph.id("a").unwrap(),
+            graph.id("d").unwrap(),
+            graph.id("e").unwrap(),
+        );
+
+        let bfs = graph.bfs(a);
+        assert_eq!(bfs.cost(e), Some(3));
+        assert_eq!(labels(&graph, &bfs.path_to(d).unwrap()), ["a", "b", "d"]);
+        assert_eq!(graph.bfs(e).cost(a), None);
+
+        assert_eq!(labels(&graph, &graph.dfs(a)), ["a", "b", "d", "e", "c"]);
+
+        let dijkstra = graph.dijkstra(a, |cost| *cost);
+        assert_eq!(dijkstra.cost(e), Some(4));
+        assert_eq!(
+            labels(&graph, &dijkstra.path_to(e).unwrap()),
+            ["a", "c", "d", "e"]
+        );
+
+        let (cost, path) = graph.astar(a, e, |cost| *cost, |_| 0).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(labels(&graph, &path), ["a", "c", "d", "e"]);
+        assert_eq!(graph.astar(e, a, |cost| *cost, |_| 0), None);
+    }
+
+    #[test]
+    fn orders_nodes() {
+        let mut graph = example();
+        assert_eq!(graph.topological_sort(), None);
+
+        let components = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|component| labels(&graph, component))
+            .collect::<Vec<_>>();
+        assert_eq!(components.len(), 4);
+        assert_eq!(components[0], ["a"]);
+        assert_eq!(components[3], ["d", "e"]);
+
+        let mut acyclic: Graph<&str> = Graph::new();
+        acyclic.add_edge("shirt", "tie", ());
+        acyclic.add_edge("tie", "jacket", ());
+        acyclic.add_edge("shirt", "belt", ());
+        acyclic.add_edge("belt", "jacket", ());
+        let order = acyclic.topological_sort().unwrap();
+        assert_eq!(*acyclic.label(order[0]), "shirt");
+        assert_eq!(*acyclic.label(order[3]), "jacket");
+
+        graph.intern("lonely");
+        assert_eq!(graph.strongly_connected_components().len(), 5);
+    }
+}
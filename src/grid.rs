@@ -0,0 +1,559 @@
+/// A dense 2D grid for puzzles whose input is a map of characters.
+/// Positions are `(x, y)` tuples, with `x` growing to the right and `y` growing downwards.
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::point::{Dir4, Dir8, Point, Vec2};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A line is longer or shorter than the first line.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} cells like the first line, found {found}"
+            ),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells is not a multiple of `width`.
+    #[must_use]
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {width}",
+            cells.len()
+        );
+
+        let height = cells.len().checked_div(width).unwrap_or(0);
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one row per line, mapping every character to a cell.
+    /// Trailing empty lines are ignored, all other lines must have the same length. Spaces are cells like any other character.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (index, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let len_before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let found = cells.len() - len_before;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(ParseGridError::Ragged {
+                        line: index + 1,
+                        expected,
+                        found,
+                    })
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Converts a point to the indices of its cell, returns [`None`] if it lies outside of the grid.
+    #[must_use]
+    pub fn index_of(&self, point: Point) -> Option<(usize, usize)> {
+        point.to_index().filter(|(x, y)| self.contains(*x, *y))
+    }
+
+    #[must_use]
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        let (x, y) = self.index_of(point)?;
+        self.get(x, y)
+    }
+
+    pub fn get_point_mut(&mut self, point: Point) -> Option<&mut T> {
+        let (x, y) = self.index_of(point)?;
+        self.get_mut(x, y)
+    }
+
+    /// Offsets a position, returns [`None`] if the result lies outside of the grid.
+    #[must_use]
+    pub fn offset(&self, x: usize, y: usize, offset: impl Into<Vec2>) -> Option<(usize, usize)> {
+        self.index_of(Point::from_index((x, y)) + offset.into())
+    }
+
+    /// The positions of the up to 4 orthogonal neighbours of a position, clockwise starting at the top.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(x, y, dir))
+    }
+
+    /// The positions of the up to 8 surrounding neighbours of a position, clockwise starting at the top.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(x, y, dir))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// # Panics
+    /// If `y` lies outside of the grid.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// # Panics
+    /// If `x` lies outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The diagonals running from top left to bottom right, starting with the one in the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        (1 - height..width).map(move |start| {
+            (0..height)
+                .filter(move |y| (0..width).contains(&(start + y)))
+                .map(move |y| &self.cells[(y * width + start + y) as usize])
+        })
+    }
+
+    /// The diagonals running from top right to bottom left, starting with the one in the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        (0..width + height - 1).map(move |start| {
+            (0..height)
+                .filter(move |y| (0..width).contains(&(start - y)))
+                .map(move |y| &self.cells[(y * width + start - y) as usize])
+        })
+    }
+
+    /// Maps every cell, keeping the shape of the grid.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The horizontal runs of consecutive cells that match `predicate`, row by row from left to right.
+    pub fn spans_by<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Span<&'a [T]>> + 'a {
+        let (mut x, mut y) = (0, 0);
+
+        std::iter::from_fn(move || {
+            while y < self.height {
+                let row = self.row(y);
+
+                while x < self.width && !predicate(&row[x]) {
+                    x += 1;
+                }
+
+                if x < self.width {
+                    let start = x;
+
+                    while x < self.width && predicate(&row[x]) {
+                        x += 1;
+                    }
+
+                    return Some(Span {
+                        start: Point::from_index((start, y)),
+                        end: Point::from_index((x - 1, y)),
+                        value: &row[start..x],
+                    });
+                }
+
+                x = 0;
+                y += 1;
+            }
+
+            None
+        })
+    }
+
+    /// The horizontal runs of digits read as decimal numbers, `digit` returns the value of a digit cell.
+    pub fn number_spans<'a>(
+        &'a self,
+        digit: impl Fn(&T) -> Option<u32> + Copy + 'a,
+    ) -> impl Iterator<Item = Span<u64>> + 'a {
+        self.spans_by(move |cell| digit(cell).is_some())
+            .map(move |span| {
+                span.map(|cells| {
+                    cells
+                        .iter()
+                        .filter_map(digit)
+                        .fold(0, |value, digit| value * 10 + u64::from(digit))
+                })
+            })
+    }
+
+    /// The cells around the bounding box of a span that lie inside of the grid.
+    pub fn span_neighbors<'a, V>(
+        &'a self,
+        span: &Span<V>,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        row_neighbors(span.start, span.end)
+            .filter_map(|point| Some((point, self.get_point(point)?)))
+    }
+}
+
+/// A horizontal run of cells, e.g. a number in a schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span<V> {
+    /// The leftmost cell.
+    pub start: Point,
+    /// The rightmost cell, inclusive.
+    pub end: Point,
+    pub value: V,
+}
+
+impl<V> Span<V> {
+    /// The number of cells in the span.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        (self.end.x - self.start.x + 1) as usize
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Point> {
+        let y = self.start.y;
+        (self.start.x..=self.end.x).map(move |x| Point::new(x, y))
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.y == self.start.y && (self.start.x..=self.end.x).contains(&point.x)
+    }
+
+    /// Whether `point` touches the span, including diagonally, without being part of it.
+    #[must_use]
+    pub fn is_adjacent(&self, point: Point) -> bool {
+        (self.start.y - 1..=self.start.y + 1).contains(&point.y)
+            && (self.start.x - 1..=self.end.x + 1).contains(&point.x)
+            && !self.contains(point)
+    }
+
+    /// The points around the bounding box of the span: the row above, both ends and the row below.
+    /// Points may lie outside of the grid, see [`Grid::span_neighbors`].
+    pub fn neighbors(&self) -> impl Iterator<Item = Point> {
+        row_neighbors(self.start, self.end)
+    }
+
+    #[must_use]
+    pub fn map<U>(self, f: impl FnOnce(V) -> U) -> Span<U> {
+        Span {
+            start: self.start,
+            end: self.end,
+            value: f(self.value),
+        }
+    }
+}
+
+/// The points around a horizontal run of cells from `start` to `end`.
+fn row_neighbors(start: Point, end: Point) -> impl Iterator<Item = Point> {
+    let (y, xs) = (start.y, start.x - 1..=end.x + 1);
+
+    xs.clone()
+        .map(move |x| Point::new(x, y - 1))
+        .chain([Point::new(*xs.start(), y), Point::new(*xs.end(), y)])
+        .chain(xs.map(move |x| Point::new(x, y + 1)))
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid where every cell is `value`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.remap(height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Builds a grid of the given size, where each position takes the cell at `source(x, y)` of this grid.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (x, y) = source(x, y);
+                self.cells[y * self.width + x].clone()
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("{point} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_point_mut(point)
+            .unwrap_or_else(|| panic!("{point} is out of bounds"))
+    }
+}
+
+/// Prints one line per row, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::point::{Dir4, Point};
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|line| line.collect()).collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid[(1, 0)], 'b');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        assert_eq!(
+            Grid::parse("ab\nabc", |c| c),
+            Err(ParseGridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let open = Grid::parse("a \nb \n\n", |c| c).unwrap();
+        assert_eq!((open.width(), open.height()), (2, 2));
+        assert_eq!(open.get(1, 1), Some(&' '));
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8(1, 0).count(), 5);
+        assert_eq!(grid.offset(2, 1, Dir4::Right), None);
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(
+            grid.neighbors8(1, 1).map(|p| grid[p]).collect::<String>(),
+            "bcfda"
+        );
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(collect(grid.columns()), vec!["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn finds_spans() {
+        let grid = Grid::parse("467..114\n...*....\n..35.633", |c| c).unwrap();
+        let numbers = grid.number_spans(|c| c.to_digit(10)).collect::<Vec<_>>();
+
+        assert_eq!(
+            numbers.iter().map(|x| x.value).collect::<Vec<_>>(),
+            vec![467, 114, 35, 633]
+        );
+        assert_eq!(numbers[1].start, Point::new(5, 0));
+        assert_eq!(numbers[1].end, Point::new(7, 0));
+        assert_eq!(numbers[1].width(), 3);
+        assert!(numbers[0].is_adjacent(Point::new(3, 1)));
+        assert!(!numbers[1].is_adjacent(Point::new(3, 1)));
+        assert_eq!(numbers[2].neighbors().count(), 10);
+        assert_eq!(
+            grid.span_neighbors(&numbers[0])
+                .filter(|(_, c)| **c == '*')
+                .map(|(point, _)| point)
+                .collect::<Vec<_>>(),
+            vec![Point::new(3, 1)]
+        );
+
+        let dots = grid.spans_by(|c| *c == '.').collect::<Vec<_>>();
+        assert_eq!(dots.len(), 5);
+        assert_eq!(dots[0].value, &['.', '.']);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
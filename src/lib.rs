@@ -1,4 +1,11 @@
+pub mod cycle;
 mod day;
+pub mod from_line;
+pub mod graph;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod ranges;
 pub mod template;
 
 pub use day::*;
@@ -1,10 +1,15 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, profile, read, scaffold, solve, test};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
+    use std::time::Duration;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::commands::all::Filter;
+    use advent_of_code::template::config;
+    use advent_of_code::template::logger::Verbosity;
+    use advent_of_code::template::test_report::Report;
+    use advent_of_code::{Day, DaySet};
 
     pub enum AppArguments {
         Download {
@@ -15,16 +20,36 @@ mod args {
         },
         Scaffold {
             day: Day,
+            fallible: bool,
         },
         Solve {
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            example: Option<Option<u8>>,
+            verbosity: Verbosity,
+            viz: Option<Option<String>>,
         },
         All {
             release: bool,
             time: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+            filter: Filter,
+            reports: Vec<Report>,
+        },
+        Profile {
+            day: Day,
+            part: Option<u8>,
+            seconds: f64,
+        },
+        Test {
+            day: Day,
+            release: bool,
+            part: Option<u8>,
+            verbosity: Verbosity,
         },
     }
 
@@ -35,21 +60,86 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                jobs: args
+                    .opt_value_from_str(["-j", "--jobs"])?
+                    .unwrap_or(config::get().all.jobs),
+                timeout: args
+                    .opt_value_from_str::<_, f64>("--timeout")?
+                    .map(Duration::from_secs_f64)
+                    .or_else(|| config::get().all.timeout()),
+                filter: Filter {
+                    days: args
+                        .opt_value_from_str("--days")?
+                        .unwrap_or_else(DaySet::all),
+                    part: parse_part(&mut args)?,
+                    skip_slow: args.contains("--skip-slow"),
+                    solved: match (
+                        args.contains("--only-solved"),
+                        args.contains("--only-unsolved"),
+                    ) {
+                        (true, true) => {
+                            eprintln!(
+                                "`--only-solved` and `--only-unsolved` are mutually exclusive."
+                            );
+                            process::exit(1);
+                        }
+                        (true, false) => Some(true),
+                        (false, true) => Some(false),
+                        (false, false) => None,
+                    },
+                },
+                reports: args.values_from_str("--report")?,
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("profile") => AppArguments::Profile {
+                day: args.free_from_str()?,
+                part: parse_part(&mut args)?,
+                seconds: args.opt_value_from_str("--seconds")?.unwrap_or(5_f64),
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
+                fallible: args.contains("--fallible"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let input = args.opt_value_from_str("--input")?;
+                let verbosity = parse_verbosity(&mut args);
+                let viz = match args.opt_value_from_str("--viz-dir")? {
+                    Some(dir) => Some(Some(dir)),
+                    None if args.contains("--viz") => Some(None),
+                    None => None,
+                };
+                // `--example` takes an optional part, which is left over as the last free argument.
+                let example = if args.contains("--example") {
+                    Some(args.opt_free_from_str()?)
+                } else {
+                    None
+                };
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    time,
+                    submit,
+                    input,
+                    example,
+                    verbosity,
+                    viz,
+                }
+            }
+            Some("test") => AppArguments::Test {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                part: parse_part(&mut args)?,
+                verbosity: parse_verbosity(&mut args),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,6 +158,29 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses the `--part <1|2>` argument of `all`, `profile` and `test`.
+    fn parse_part(args: &mut pico_args::Arguments) -> Result<Option<u8>, pico_args::Error> {
+        args.opt_value_from_fn("--part", |value| match value.parse::<u8>() {
+            Ok(part @ (1 | 2)) => Ok(part),
+            _ => Err("expected 1 or 2"),
+        })
+    }
+
+    fn parse_verbosity(args: &mut pico_args::Arguments) -> Verbosity {
+        let quiet = args.contains(["-q", "--quiet"]);
+        let mut verbose = 0;
+
+        while args.contains(["-v", "--verbose"]) {
+            verbose += 1;
+        }
+
+        if args.contains("-vv") {
+            verbose += 2;
+        }
+
+        Verbosity::new(quiet, verbose)
+    }
 }
 
 fn main() {
@@ -77,16 +190,34 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                jobs,
+                timeout,
+                filter,
+                reports,
+            } => all::handle(release, time, jobs, timeout, &filter, &reports),
             AppArguments::Download { day } => download::handle(day),
+            AppArguments::Profile { day, part, seconds } => profile::handle(day, part, seconds),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Scaffold { day, fallible } => scaffold::handle(day, fallible),
             AppArguments::Solve {
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+                example,
+                verbosity,
+                viz,
+            } => solve::handle(day, release, time, submit, input, example, verbosity, viz),
+            AppArguments::Test {
+                day,
+                release,
+                part,
+                verbosity,
+            } => test::handle(day, release, part, verbosity),
         },
     };
 }
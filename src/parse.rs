@@ -0,0 +1,544 @@
+/// Zero-allocation parsing of the numbers, labels and blocks that most inputs consist of.
+/// Every function works on `&str` as well as on `&[u8]`, and errors report the line they occurred on:
+///
+/// ```
+/// # use advent_of_code::parse;
+/// let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+/// let mut lines = parse::lines(input);
+///
+/// let times = lines.next().unwrap().after("Time")?.unsigned().collect::<Result<Vec<u32>, _>>()?;
+/// let [a, b, c] = lines.next().unwrap().array::<u32, 3>()?;
+///
+/// assert_eq!(times, vec![7, 15, 30]);
+/// assert_eq!([a, b, c], [9, 40, 200]);
+/// # Ok::<(), parse::ParseError>(())
+/// ```
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::{any, str};
+
+/// An integer type that numbers can be parsed into.
+pub trait Number: Copy + Default {
+    const SIGNED: bool;
+
+    /// Appends an ASCII digit, subtracting it for negative numbers.
+    /// Returns [`None`] if the number does not fit into the type.
+    fn push_digit(self, negative: bool, digit: u8) -> Option<Self>;
+
+    /// Reads ASCII digits, returns [`None`] if the number does not fit into the type.
+    fn from_digits(negative: bool, digits: &[u8]) -> Option<Self> {
+        digits.iter().try_fold(Self::default(), |value, digit| {
+            value.push_digit(negative, *digit)
+        })
+    }
+}
+
+macro_rules! impl_number {
+    ($signed:expr; $($ty:ty),*) => {
+        $(
+            impl Number for $ty {
+                const SIGNED: bool = $signed;
+
+                fn push_digit(self, negative: bool, digit: u8) -> Option<Self> {
+                    let digit = (digit - b'0') as $ty;
+                    let value = self.checked_mul(10)?;
+
+                    if negative {
+                        value.checked_sub(digit)
+                    } else {
+                        value.checked_add(digit)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(false; u8, u16, u32, u64, u128, usize);
+impl_number!(true; i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A number does not fit into the requested type, e.g. a negative number for an unsigned type.
+    OutOfRange {
+        number: String,
+        ty: &'static str,
+    },
+    /// A line contains fewer numbers than requested.
+    MissingNumbers {
+        expected: usize,
+        found: usize,
+    },
+    /// A line does not contain `label:`.
+    MissingLabel(String),
+    InvalidUtf8,
+    /// A solution specific error, see [`Line::error`].
+    Invalid(String),
+}
+
+/// An error while parsing the input, with the 1-based number of the line it occurred on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub kind: ErrorKind,
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.kind {
+            ErrorKind::OutOfRange { number, ty } => write!(f, "{number} does not fit into {ty}"),
+            ErrorKind::MissingNumbers { expected, found } => {
+                write!(f, "expected {expected} numbers, found {found}")
+            }
+            ErrorKind::MissingLabel(label) => write!(f, "expected \"{label}:\""),
+            ErrorKind::InvalidUtf8 => write!(f, "line is not valid UTF-8"),
+            ErrorKind::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// A line of the input, remembering its line number for errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    number: usize,
+    text: &'a [u8],
+}
+
+impl<'a> Line<'a> {
+    /// Wraps text that was found on line `number`, e.g. a capture of a regex.
+    pub fn new(number: usize, text: &'a (impl AsRef<[u8]> + ?Sized)) -> Self {
+        Self {
+            number,
+            text: text.as_ref(),
+        }
+    }
+
+    /// The 1-based line number.
+    #[must_use]
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.text
+    }
+
+    pub fn as_str(&self) -> Result<&'a str, ParseError> {
+        str::from_utf8(self.text).map_err(|_| self.error_kind(ErrorKind::InvalidUtf8))
+    }
+
+    /// All runs of digits, a `-` in front of a number is ignored.
+    #[must_use]
+    pub fn unsigned<T: Number>(&self) -> Numbers<'a, T> {
+        Numbers::new(*self, false)
+    }
+
+    /// All runs of digits, a `-` right in front of a number makes it negative.
+    #[must_use]
+    pub fn signed<T: Number>(&self) -> Numbers<'a, T> {
+        Numbers::new(*self, true)
+    }
+
+    /// The first `N` numbers, which are signed if `T` is a signed type.
+    pub fn array<T: Number, const N: usize>(&self) -> Result<[T; N], ParseError> {
+        let mut numbers = Numbers::new(*self, T::SIGNED);
+        let mut array = [T::default(); N];
+
+        for (found, slot) in array.iter_mut().enumerate() {
+            *slot = numbers.next().ok_or_else(|| {
+                self.error_kind(ErrorKind::MissingNumbers { expected: N, found })
+            })??;
+        }
+
+        Ok(array)
+    }
+
+    /// All digits of the line read as a single number, ignoring anything in between.
+    pub fn joined_digits<T: Number>(&self) -> Result<T, ParseError> {
+        let mut value = T::default();
+        let mut found = false;
+
+        for digits in self.text.split(|x| !x.is_ascii_digit()) {
+            for digit in digits {
+                value = value
+                    .push_digit(false, *digit)
+                    .ok_or_else(|| self.out_of_range::<T>(self.text))?;
+                found = true;
+            }
+        }
+
+        if found {
+            Ok(value)
+        } else {
+            Err(self.error_kind(ErrorKind::MissingNumbers {
+                expected: 1,
+                found: 0,
+            }))
+        }
+    }
+
+    /// The rest of the line after `label:`, e.g. the numbers after `seeds:`.
+    pub fn after(&self, label: &str) -> Result<Self, ParseError> {
+        let mut from = 0;
+
+        // the label may also appear without a colon, e.g. in `seeds seeds: 1 2`.
+        while let Some(index) = self
+            .text
+            .get(from..)
+            .and_then(|rest| find(rest, label.as_bytes()))
+        {
+            let end = from + index + label.len();
+            if self.text.get(end) == Some(&b':') {
+                return Ok(self.slice(end + 1, self.text.len()));
+            }
+            from += index + 1;
+        }
+
+        Err(self.error_kind(ErrorKind::MissingLabel(label.to_string())))
+    }
+
+    /// Splits the line at the first `separator`.
+    #[must_use]
+    pub fn split_once(&self, separator: &str) -> Option<(Self, Self)> {
+        let index = find(self.text, separator.as_bytes())?;
+        Some((
+            self.slice(0, index),
+            self.slice(index + separator.len(), self.text.len()),
+        ))
+    }
+
+    /// Splits the line at every `separator`.
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Line<'a>> {
+        let mut rest = Some(*self);
+
+        std::iter::from_fn(move || {
+            let line = rest?;
+            match line.split_once(separator) {
+                Some((part, next)) => {
+                    rest = Some(next);
+                    Some(part)
+                }
+                None => rest.take(),
+            }
+        })
+    }
+
+    /// Parses the whole line with [`FromStr`](str::FromStr), e.g. a struct declared with [`from_line!`](crate::from_line!).
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: str::FromStr,
+        T::Err: Display,
+    {
+        self.as_str()?.parse().map_err(|err| self.error(err))
+    }
+
+    /// Creates an error on this line, e.g. for an unknown keyword.
+    #[must_use]
+    pub fn error(&self, message: impl Display) -> ParseError {
+        self.error_kind(ErrorKind::Invalid(message.to_string()))
+    }
+
+    fn error_kind(&self, kind: ErrorKind) -> ParseError {
+        ParseError {
+            line: self.number,
+            kind,
+        }
+    }
+
+    fn out_of_range<T>(&self, number: &[u8]) -> ParseError {
+        self.error_kind(ErrorKind::OutOfRange {
+            number: String::from_utf8_lossy(number).into_owned(),
+            ty: any::type_name::<T>(),
+        })
+    }
+
+    fn slice(&self, start: usize, end: usize) -> Self {
+        Self {
+            number: self.number,
+            text: &self.text[start..end],
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// The numbers of a line, see [`Line::unsigned`] and [`Line::signed`].
+#[derive(Debug, Clone)]
+pub struct Numbers<'a, T> {
+    line: Line<'a>,
+    rest: &'a [u8],
+    signed: bool,
+    number: PhantomData<T>,
+}
+
+impl<'a, T> Numbers<'a, T> {
+    fn new(line: Line<'a>, signed: bool) -> Self {
+        Self {
+            line,
+            rest: line.text,
+            signed,
+            number: PhantomData,
+        }
+    }
+}
+
+impl<T: Number> Iterator for Numbers<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.rest.iter().position(u8::is_ascii_digit)?;
+        let len = self.rest[start..]
+            .iter()
+            .position(|x| !x.is_ascii_digit())
+            .unwrap_or(self.rest.len() - start);
+
+        let negative = self.signed && start > 0 && self.rest[start - 1] == b'-';
+        let text = &self.rest[start - usize::from(negative)..start + len];
+        let digits = &self.rest[start..start + len];
+        self.rest = &self.rest[start + len..];
+
+        Some(T::from_digits(negative, digits).ok_or_else(|| self.line.out_of_range::<T>(text)))
+    }
+}
+
+/// Splits `input` into lines like [`str::lines`], numbered from 1.
+pub fn lines<'a>(input: &'a (impl AsRef<[u8]> + ?Sized)) -> Lines<'a> {
+    Lines {
+        rest: input.as_ref(),
+        number: 1,
+    }
+}
+
+/// The lines of an input or block, see [`lines`].
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    rest: &'a [u8],
+    number: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (text, rest) = split_line(self.rest);
+        let line = Line {
+            number: self.number,
+            text,
+        };
+
+        self.rest = rest;
+        self.number += 1;
+        Some(line)
+    }
+}
+
+/// Splits off the first line, without its `\n` or `\r\n`.
+fn split_line(input: &[u8]) -> (&[u8], &[u8]) {
+    let (line, rest) = match input.iter().position(|x| *x == b'\n') {
+        Some(index) => (&input[..index], &input[index + 1..]),
+        None => (input, &input[input.len()..]),
+    };
+
+    (line.strip_suffix(b"\r").unwrap_or(line), rest)
+}
+
+/// Splits `input` into blocks of lines that are separated by blank lines.
+pub fn blocks<'a>(input: &'a (impl AsRef<[u8]> + ?Sized)) -> Blocks<'a> {
+    Blocks {
+        rest: input.as_ref(),
+        number: 1,
+    }
+}
+
+/// A block of consecutive non-blank lines, see [`blocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    first_line: usize,
+    text: &'a [u8],
+}
+
+impl<'a> Block<'a> {
+    /// The number of the first line of the block.
+    #[must_use]
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.text
+    }
+
+    /// The lines of the block, numbered as in the whole input.
+    #[must_use]
+    pub fn lines(&self) -> Lines<'a> {
+        Lines {
+            rest: self.text,
+            number: self.first_line,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    rest: &'a [u8],
+    number: usize,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip the blank lines in front of the block.
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let (line, rest) = split_line(self.rest);
+            if !line.is_empty() {
+                break;
+            }
+
+            self.rest = rest;
+            self.number += 1;
+        }
+
+        let block = self.rest;
+        let first_line = self.number;
+        let mut len = 0;
+
+        while !self.rest.is_empty() {
+            let (line, rest) = split_line(self.rest);
+            if line.is_empty() {
+                break;
+            }
+
+            len = block.len() - self.rest.len() + line.len();
+            self.rest = rest;
+            self.number += 1;
+        }
+
+        Some(Block {
+            first_line,
+            text: &block[..len],
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, lines, ErrorKind, Line, ParseError};
+
+    #[test]
+    fn parses_numbers() {
+        let line = Line::new(3, "x=-12, y=7..-3");
+
+        assert_eq!(
+            line.unsigned::<u32>().collect::<Result<Vec<_>, _>>(),
+            Ok(vec![12, 7, 3])
+        );
+        assert_eq!(
+            line.signed::<i64>().collect::<Result<Vec<_>, _>>(),
+            Ok(vec![-12, 7, -3])
+        );
+        assert_eq!(line.array::<i8, 2>(), Ok([-12, 7]));
+        assert_eq!(line.joined_digits::<u64>(), Ok(1273));
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let line = Line::new(3, b"300 -1".as_slice());
+
+        assert_eq!(
+            line.array::<u8, 1>(),
+            Err(ParseError {
+                line: 3,
+                kind: ErrorKind::OutOfRange {
+                    number: "300".into(),
+                    ty: "u8"
+                }
+            })
+        );
+        assert_eq!(
+            line.signed::<u32>()
+                .nth(1)
+                .unwrap()
+                .unwrap_err()
+                .to_string(),
+            "line 3: -1 does not fit into u32"
+        );
+        assert_eq!(
+            line.array::<u32, 3>().unwrap_err().to_string(),
+            "line 3: expected 3 numbers, found 2"
+        );
+        assert_eq!(
+            line.after("seeds").unwrap_err().kind,
+            ErrorKind::MissingLabel("seeds".into())
+        );
+    }
+
+    #[test]
+    fn splits_lines() {
+        let line = Line::new(1, "Game 1: 3 blue, 4 red; 1 red");
+        let (game, rounds) = line.split_once(": ").unwrap();
+
+        assert_eq!(game.array::<u32, 1>(), Ok([1]));
+        assert_eq!(
+            rounds
+                .split("; ")
+                .map(|round| round.as_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["3 blue, 4 red", "1 red"]
+        );
+        assert_eq!(
+            line.after("Game 1").unwrap().as_str(),
+            Ok(" 3 blue, 4 red; 1 red")
+        );
+        assert_eq!(
+            Line::new(1, "seeds seeds: 1 2")
+                .after("seeds")
+                .unwrap()
+                .as_str(),
+            Ok(" 1 2")
+        );
+        assert!(Line::new(1, "ab").after("").is_err());
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "seeds: 79 14\r\n\r\nseed-to-soil map:\n50 98 2\n52 50 48\n\n\nsoil-to-fertilizer map:\n0 15 37\n";
+
+        let numbers = lines(input).map(|line| line.number()).collect::<Vec<_>>();
+        assert_eq!(numbers, (1..=9).collect::<Vec<_>>());
+
+        let blocks = blocks(input).collect::<Vec<_>>();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].as_bytes(), b"seeds: 79 14");
+        assert_eq!(blocks[1].first_line(), 3);
+        assert_eq!(
+            blocks[2]
+                .lines()
+                .map(|line| (line.number(), line.as_str().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![(8, "soil-to-fertilizer map:"), (9, "0 15 37")]
+        );
+    }
+}
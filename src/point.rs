@@ -0,0 +1,365 @@
+/// Integer points, offsets and compass directions for grid puzzles.
+/// Coordinates follow the grid convention: `x` grows to the right and `y` grows downwards, so `Up` is `(0, -1)`.
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position, which may lie outside of any grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The offset between two points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> u64 {
+        (other - self).manhattan_len()
+    }
+
+    /// The number of king moves between two points, `1` for all 8 neighbours.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> u64 {
+        (other - self).chebyshev_len()
+    }
+
+    /// The point one step into `dir`.
+    #[must_use]
+    pub fn step(self, dir: impl Into<Vec2>) -> Self {
+        self + dir.into()
+    }
+
+    /// The 4 orthogonal neighbours, clockwise starting at the top.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// The 8 surrounding neighbours, clockwise starting at the top.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// Converts the point to `(x, y)` grid indices, returns [`None`] for negative coordinates.
+    #[must_use]
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    /// Converts `(x, y)` grid indices to a point.
+    ///
+    /// # Panics
+    /// If an index does not fit into an `i64`.
+    #[must_use]
+    pub fn from_index((x, y): (usize, usize)) -> Self {
+        Self::new(
+            i64::try_from(x).expect("x index fits into i64"),
+            i64::try_from(y).expect("y index fits into i64"),
+        )
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub fn manhattan_len(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    #[must_use]
+    pub fn chebyshev_len(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl Add<Vec2> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// The 4 orthogonal directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Rotates by `steps` quarter turns, clockwise for positive and counter-clockwise for negative steps.
+    #[must_use]
+    pub fn rotate(self, steps: i32) -> Self {
+        Self::ALL[(self as i32 + steps).rem_euclid(4) as usize]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.rotate(2)
+    }
+
+    #[must_use]
+    pub fn to_vec2(self) -> Vec2 {
+        Dir8::from(self).to_vec2()
+    }
+}
+
+/// The 8 compass directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Rotates by `steps` eighth turns, clockwise for positive and counter-clockwise for negative steps.
+    #[must_use]
+    pub fn rotate(self, steps: i32) -> Self {
+        Self::ALL[(self as i32 + steps).rem_euclid(8) as usize]
+    }
+
+    /// Turns by 90 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    #[must_use]
+    pub fn to_vec2(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::UpRight => Vec2::new(1, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::DownRight => Vec2::new(1, 1),
+            Self::Down => Vec2::new(0, 1),
+            Self::DownLeft => Vec2::new(-1, 1),
+            Self::Left => Vec2::new(-1, 0),
+            Self::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Self::Up,
+            Dir4::Right => Self::Right,
+            Dir4::Down => Self::Down,
+            Dir4::Left => Self::Left,
+        }
+    }
+}
+
+impl From<Dir4> for Vec2 {
+    fn from(dir: Dir4) -> Self {
+        dir.to_vec2()
+    }
+}
+
+impl From<Dir8> for Vec2 {
+    fn from(dir: Dir8) -> Self {
+        dir.to_vec2()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Point, Vec2};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+
+        assert_eq!(b - a, Vec2::new(-4, 3));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - Vec2::new(1, 2), Point::ORIGIN);
+        assert_eq!(-Vec2::new(1, -2) * 3, Vec2::new(-3, 6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.step(Dir4::Up), Point::new(1, 1));
+    }
+
+    #[test]
+    fn converts_to_indices() {
+        assert_eq!(Point::new(3, 0).to_index(), Some((3, 0)));
+        assert_eq!(Point::new(-1, 0).to_index(), None);
+        assert_eq!(Point::new(0, -1).to_index(), None);
+        assert_eq!(Point::from_index((4, 2)), Point::new(4, 2));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir4::Down.rotate(-3), Dir4::Left);
+
+        assert_eq!(Dir8::Up.rotate(1), Dir8::UpRight);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::UpRight);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+
+        for dir in Dir8::ALL {
+            assert_eq!(dir.opposite().to_vec2(), -dir.to_vec2());
+            assert_eq!(dir.to_vec2().chebyshev_len(), 1);
+        }
+    }
+
+    #[test]
+    fn lists_neighbors() {
+        let point = Point::new(0, 0);
+        assert!(point.neighbors4().all(|x| x.manhattan(point) == 1));
+        assert_eq!(
+            point
+                .neighbors8()
+                .filter(|x| x.chebyshev(point) == 1)
+                .count(),
+            8
+        );
+    }
+}
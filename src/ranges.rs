@@ -0,0 +1,484 @@
+/// Sets of half-open integer intervals, and maps that shift intervals piece by piece.
+/// Both work on whole ranges at a time, so inputs with billions of values can be processed by splitting a handful of intervals.
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+/// A set of integers stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl RangeSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts and merges ranges, dropping empty ones.
+    fn normalize(mut ranges: Vec<Range<i64>>) -> Self {
+        ranges.retain(|range| !range.is_empty());
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalize(ranges);
+    }
+
+    /// The ranges of the set in ascending order.
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in the set.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| range.start.abs_diff(range.end))
+            .sum()
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|range| range.end - 1)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(&value))
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (0, 0);
+
+        while let (Some(left), Some(right)) = (self.ranges.get(a), other.ranges.get(b)) {
+            let overlap = intersection(left, right);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+
+            // advance the range that ends first, it can not overlap anything else.
+            if left.end <= right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            for cut in other
+                .ranges
+                .iter()
+                .filter(|cut| cut.start < range.end && range.start < cut.end)
+            {
+                if start < cut.start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl From<Range<i64>> for RangeSet {
+    fn from(range: Range<i64>) -> Self {
+        Self::normalize(vec![range])
+    }
+}
+
+impl Display for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (index, range) in self.ranges.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}..{}", range.start, range.end)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+fn intersection(a: &Range<i64>, b: &Range<i64>) -> Range<i64> {
+    a.start.max(b.start)..a.end.min(b.end)
+}
+
+fn shift(range: &Range<i64>, offset: i64) -> Range<i64> {
+    range.start + offset..range.end + offset
+}
+
+/// A piecewise-linear map with slope 1: every piece shifts a range of values by an offset,
+/// values outside of all pieces map to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeMap {
+    /// Sorted, disjoint source ranges with their offsets, offsets are never `0`.
+    pieces: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+    /// The identity map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` to `source.start + offset..source.end + offset`.
+    ///
+    /// # Panics
+    /// If `source` overlaps a range that was inserted before, see [`RangeMap::try_insert`].
+    pub fn insert(&mut self, source: Range<i64>, offset: i64) {
+        if let Err(err) = self.try_insert(source, offset) {
+            panic!("{err}");
+        }
+    }
+
+    /// Maps `source` to `source.start + offset..source.end + offset`, unless `source` overlaps a range that was inserted before.
+    pub fn try_insert(&mut self, source: Range<i64>, offset: i64) -> Result<(), OverlapError> {
+        if source.is_empty() || offset == 0 {
+            return Ok(());
+        }
+
+        let index = self
+            .pieces
+            .partition_point(|(range, _)| range.start < source.start);
+
+        let previous = index
+            .checked_sub(1)
+            .map(|index| &self.pieces[index].0)
+            .filter(|range| range.end > source.start);
+        let next = self
+            .pieces
+            .get(index)
+            .map(|(range, _)| range)
+            .filter(|range| range.start < source.end);
+
+        if let Some(other) = previous.or(next) {
+            return Err(OverlapError {
+                source,
+                other: other.clone(),
+            });
+        }
+
+        self.pieces.insert(index, (source, offset));
+        Ok(())
+    }
+
+    /// Collects pieces like [`FromIterator`], but returns an error instead of panicking if two source ranges overlap.
+    pub fn try_from_pieces(
+        pieces: impl IntoIterator<Item = (Range<i64>, i64)>,
+    ) -> Result<Self, OverlapError> {
+        let mut map = Self::new();
+        for (source, offset) in pieces {
+            map.try_insert(source, offset)?;
+        }
+        Ok(map)
+    }
+
+    /// The pieces of the map in ascending order of their source ranges.
+    #[must_use]
+    pub fn pieces(&self) -> &[(Range<i64>, i64)] {
+        &self.pieces
+    }
+
+    /// The pieces together with the identity ranges between them, covering all of `i64::MIN..i64::MAX`.
+    fn segments(&self) -> Vec<(Range<i64>, i64)> {
+        let mut segments = vec![];
+        let mut start = i64::MIN;
+
+        for (range, offset) in &self.pieces {
+            if start < range.start {
+                segments.push((start..range.start, 0));
+            }
+            segments.push((range.clone(), *offset));
+            start = range.end;
+        }
+
+        if start < i64::MAX {
+            segments.push((start..i64::MAX, 0));
+        }
+
+        segments
+    }
+
+    #[must_use]
+    pub fn apply(&self, value: i64) -> i64 {
+        let index = self.pieces.partition_point(|(range, _)| range.end <= value);
+
+        match self.pieces.get(index) {
+            Some((range, offset)) if range.contains(&value) => value + offset,
+            _ => value,
+        }
+    }
+
+    /// Maps every value of `set`, splitting its ranges where they cross piece boundaries.
+    #[must_use]
+    pub fn apply_set(&self, set: &RangeSet) -> RangeSet {
+        let mut ranges = vec![];
+
+        for range in set.ranges() {
+            for (segment, offset) in self.segments() {
+                let overlap = intersection(range, &segment);
+                if !overlap.is_empty() {
+                    ranges.push(shift(&overlap, offset));
+                }
+            }
+        }
+
+        RangeSet::normalize(ranges)
+    }
+
+    /// All values that are mapped into `set`.
+    #[must_use]
+    pub fn preimage(&self, set: &RangeSet) -> RangeSet {
+        let mut ranges = vec![];
+
+        for (segment, offset) in self.segments() {
+            let image = shift(&segment, offset);
+
+            for range in set.ranges() {
+                let overlap = intersection(range, &image);
+                if !overlap.is_empty() {
+                    ranges.push(shift(&overlap, -offset));
+                }
+            }
+        }
+
+        RangeSet::normalize(ranges)
+    }
+
+    /// The map that applies `self` first and `then` second.
+    #[must_use]
+    pub fn compose(&self, then: &Self) -> Self {
+        let mut pieces = vec![];
+        let then_segments = then.segments();
+
+        for (segment, offset) in self.segments() {
+            let image = shift(&segment, offset);
+
+            for (then_segment, then_offset) in &then_segments {
+                let overlap = intersection(&image, then_segment);
+                if !overlap.is_empty() && offset + then_offset != 0 {
+                    pieces.push((shift(&overlap, -offset), offset + then_offset));
+                }
+            }
+        }
+
+        pieces.sort_by_key(|(range, _)| range.start);
+        Self::merged(pieces)
+    }
+
+    /// The inverse map, or [`None`] if two values map to the same value.
+    #[must_use]
+    pub fn invert(&self) -> Option<Self> {
+        let mut images = self
+            .segments()
+            .into_iter()
+            .map(|(segment, offset)| (shift(&segment, offset), -offset))
+            .collect::<Vec<_>>();
+
+        images.sort_by_key(|(range, _)| range.start);
+
+        if images
+            .windows(2)
+            .any(|pair| pair[0].0.end > pair[1].0.start)
+        {
+            return None;
+        }
+
+        Some(Self::merged(
+            images
+                .into_iter()
+                .filter(|(_, offset)| *offset != 0)
+                .collect(),
+        ))
+    }
+
+    /// Joins adjacent pieces with the same offset, `pieces` must be sorted and disjoint.
+    fn merged(pieces: Vec<(Range<i64>, i64)>) -> Self {
+        let mut merged: Vec<(Range<i64>, i64)> = Vec::with_capacity(pieces.len());
+
+        for (range, offset) in pieces {
+            match merged.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end;
+                }
+                _ => merged.push((range, offset)),
+            }
+        }
+
+        Self { pieces: merged }
+    }
+}
+
+impl FromIterator<(Range<i64>, i64)> for RangeMap {
+    /// # Panics
+    /// If two source ranges overlap, see [`RangeMap::try_from_pieces`].
+    fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+        Self::try_from_pieces(iter).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+/// A piece of a [`RangeMap`] whose source range overlaps a piece that was inserted before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapError {
+    pub source: Range<i64>,
+    pub other: Range<i64>,
+}
+
+impl std::error::Error for OverlapError {}
+
+impl Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}..{} overlaps {}..{}",
+            self.source.start, self.source.end, self.other.start, self.other.end
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{OverlapError, RangeMap, RangeSet};
+
+    fn set(ranges: &[(i64, i64)]) -> RangeSet {
+        ranges.iter().map(|(start, end)| *start..*end).collect()
+    }
+
+    /// The `seed-to-soil` map of the day 5 example.
+    fn seed_to_soil() -> RangeMap {
+        [(98..100, -48), (50..98, 2)].into_iter().collect()
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(0, 5), (3, 8), (10, 12), (12, 13)]);
+        let b = set(&[(4, 11)]);
+
+        assert_eq!(a.ranges(), &[0..8, 10..13]);
+        assert_eq!(a.len(), 11);
+        assert!(a.contains(12) && !a.contains(8));
+        assert_eq!(a.union(&b), set(&[(0, 13)]));
+        assert_eq!(a.intersect(&b), set(&[(4, 8), (10, 11)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (11, 13)]));
+        assert_eq!(b.difference(&a).to_string(), "{8..10}");
+        assert_eq!((a.min(), a.max()), (Some(0), Some(12)));
+    }
+
+    #[test]
+    fn maps_sets_with_splitting() {
+        let map = seed_to_soil();
+
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(10), 10);
+        assert_eq!(
+            map.apply_set(&set(&[(45, 100)])),
+            set(&[(45, 50), (50, 52), (52, 100)])
+        );
+        assert_eq!(
+            map.apply_set(&set(&[(96, 100)])),
+            set(&[(50, 52), (98, 100)])
+        );
+        assert_eq!(map.preimage(&set(&[(50, 52)])), set(&[(98, 100)]));
+    }
+
+    #[test]
+    fn composes_and_inverts() {
+        let map = seed_to_soil();
+        let then: RangeMap = [(15..52, -15), (52..54, -15), (0..15, 39)]
+            .into_iter()
+            .collect();
+        let composed = map.compose(&then);
+
+        for value in -5..120 {
+            assert_eq!(composed.apply(value), then.apply(map.apply(value)));
+        }
+
+        let inverse = map.invert().unwrap();
+        for value in -5..120 {
+            assert_eq!(inverse.apply(map.apply(value)), value);
+        }
+        assert_eq!(map.compose(&inverse), RangeMap::new());
+
+        let lossy: RangeMap = [(0..10, 10)].into_iter().collect();
+        assert_eq!(lossy.invert(), None);
+    }
+
+    #[test]
+    fn rejects_overlapping_pieces() {
+        let mut map = seed_to_soil();
+
+        assert_eq!(
+            map.try_insert(90..99, 1),
+            Err(OverlapError {
+                source: 90..99,
+                other: 50..98
+            })
+        );
+        assert_eq!(
+            RangeMap::try_from_pieces([(0..10, 1), (5..6, 2)])
+                .unwrap_err()
+                .to_string(),
+            "5..6 overlaps 0..10"
+        );
+        assert_eq!(map.try_insert(100..110, 1), Ok(()));
+        assert_eq!(map.apply(105), 106);
+    }
+}
@@ -0,0 +1,109 @@
+/// Conversion of the values returned by solution parts into a common [`Answer`].
+/// Parts may either return an `Option<T>` or a `Result<T, E>` where `E` converts into an [`anyhow::Error`].
+use std::error::Error;
+use std::fmt::Display;
+
+/// The outcome of running a solution part.
+#[derive(Debug)]
+pub enum Answer<T> {
+    Solved(T),
+    Unsolved,
+    Error(anyhow::Error),
+}
+
+impl<T> Answer<T> {
+    /// Converts the answer into `Ok(Some(value))`, `Ok(None)` for unsolved parts, or the error.
+    pub fn into_result(self) -> anyhow::Result<Option<T>> {
+        match self {
+            Answer::Solved(value) => Ok(Some(value)),
+            Answer::Unsolved => Ok(None),
+            Answer::Error(err) => Err(err),
+        }
+    }
+}
+
+/// A return type that solution parts may use.
+pub trait PartResult {
+    type Value: Display;
+
+    fn into_answer(self) -> Answer<Self::Value>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Value = T;
+
+    fn into_answer(self) -> Answer<T> {
+        match self {
+            Some(value) => Answer::Solved(value),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<anyhow::Error>> PartResult for Result<T, E> {
+    type Value = T;
+
+    fn into_answer(self) -> Answer<T> {
+        match self {
+            Ok(value) => Answer::Solved(value),
+            Err(err) => {
+                let err = err.into();
+                if err.is::<Unsolved>() {
+                    Answer::Unsolved
+                } else {
+                    Answer::Error(err)
+                }
+            }
+        }
+    }
+}
+
+/// An error that fallible parts return while they have not been solved yet.
+/// It is reported like a part returning [`None`] rather than as an error.
+///
+/// ```
+/// # use advent_of_code::template::answer::Unsolved;
+/// pub fn part_one(input: &str) -> anyhow::Result<u32> {
+///     Err(Unsolved.into())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Unsolved;
+
+impl Error for Unsolved {}
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not solved yet")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, PartResult, Unsolved};
+    use anyhow::Context;
+
+    #[test]
+    fn converts_options() {
+        assert!(matches!(Some(5).into_answer(), Answer::Solved(5)));
+        assert!(matches!(None::<u32>.into_answer(), Answer::Unsolved));
+    }
+
+    #[test]
+    fn converts_results() {
+        assert!(matches!(
+            Ok::<_, anyhow::Error>(5).into_answer(),
+            Answer::Solved(5)
+        ));
+        assert!(matches!(
+            Err::<u32, _>(anyhow::Error::from(Unsolved)).into_answer(),
+            Answer::Unsolved
+        ));
+
+        let result = "x".parse::<u32>().context("could not parse card");
+        let Answer::Error(err) = result.into_answer() else {
+            panic!("expected an error");
+        };
+        assert_eq!(err.chain().count(), 2);
+    }
+}
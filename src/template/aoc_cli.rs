@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::{config, paths};
 use crate::Day;
 
 #[derive(Debug)]
@@ -69,8 +70,14 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
 
     let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        paths::display_path(&paths::input_path(day))
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        paths::display_path(&paths::puzzle_path(day))
+    );
     Ok(output)
 }
 
@@ -83,18 +90,25 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    paths::input_path(day).display().to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    paths::puzzle_path(day).display().to_string()
 }
 
 fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+    config::get().year()
+}
+
+/// The session file from `aoc.toml`, unless aoc-cli gets the session from its environment variable.
+fn get_session_file() -> Option<String> {
+    if std::env::var_os("ADVENT_OF_CODE_SESSION").is_some() {
+        return None;
     }
+
+    let path = config::get().paths.session_file.as_ref()?;
+    Some(paths::project_root().join(path).display().to_string())
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -105,6 +119,11 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
         cmd_args.push(year.to_string());
     }
 
+    if let Some(session_file) = get_session_file() {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file);
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
@@ -1,38 +1,369 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::sync::mpsc;
+use std::time::Duration;
+use std::{io, process};
+
+use rayon::ThreadPoolBuilder;
 
 use crate::template::{
+    paths, progress,
     readme_benchmarks::{self, Timings},
+    readme_stars,
+    test_report::{Outcome, Report, TestCase},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet};
+use child_commands::Exit;
+
+/// Days whose selected parts took longer than this in the last benchmark are skipped by `--skip-slow`.
+const SLOW_THRESHOLD_NANOS: f64 = 1_000_000_000_f64;
+
+/// Restricts which days and parts are run.
+pub struct Filter {
+    pub days: DaySet,
+    pub part: Option<u8>,
+    pub skip_slow: bool,
+    /// Only run days that are solved (`Some(true)`) or unsolved (`Some(false)`) according to the readme.
+    pub solved: Option<bool>,
+}
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            days: DaySet::all(),
+            part: None,
+            skip_slow: false,
+            solved: None,
+        }
+    }
+}
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+impl Filter {
+    fn is_restricted(&self) -> bool {
+        self.days != DaySet::all() || self.part.is_some() || self.skip_slow || self.solved.is_some()
+    }
+
+    fn select_days(&self) -> DaySet {
+        let mut days = self.days;
+
+        if let Some(solved) = self.solved {
+            match readme_stars::read() {
+                Ok(stars) => {
+                    days = days
+                        .iter()
+                        .filter(|day| {
+                            let is_solved = stars
+                                .iter()
+                                .find(|x| x.day == *day)
+                                .is_some_and(|x| x.is_solved(self.part));
+                            is_solved == solved
+                        })
+                        .collect()
+                }
+                Err(_) => {
+                    eprintln!("Failed to read stars from readme, running solved and unsolved days.")
+                }
+            }
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        if self.skip_slow {
+            match readme_benchmarks::read() {
+                Ok(timings) => timings.iter().for_each(|timing| {
+                    let nanos = match self.part {
+                        Some(1) => timing
+                            .part_1
+                            .as_deref()
+                            .and_then(readme_benchmarks::parse_duration_nanos),
+                        Some(2) => timing
+                            .part_2
+                            .as_deref()
+                            .and_then(readme_benchmarks::parse_duration_nanos),
+                        _ => Some(timing.total_nanos),
+                    };
+
+                    if days.contains(timing.day) && nanos.is_some_and(|x| x > SLOW_THRESHOLD_NANOS)
+                    {
+                        println!("Skipping slow day {}.", timing.day);
+                        days.remove(timing.day);
+                    }
+                }),
+                Err(_) => eprintln!("Failed to read benchmarks from readme, running slow days."),
+            }
+        }
+
+        days
+    }
+}
+
+/// The overall result of running a day's solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayStatus {
+    Solved,
+    Unsolved,
+    WrongAnswer,
+    Errored,
+    Panicked,
+    TimedOut,
+    BuildFailed,
+}
+
+impl DayStatus {
+    const ALL: [DayStatus; 7] = [
+        Self::Solved,
+        Self::Unsolved,
+        Self::WrongAnswer,
+        Self::Errored,
+        Self::Panicked,
+        Self::TimedOut,
+        Self::BuildFailed,
+    ];
+
+    fn from_output(output: &child_commands::Output) -> Self {
+        let any_line = |pattern: &dyn Fn(&String) -> bool| output.stdout.iter().any(pattern);
+
+        match output.exit {
+            Exit::NotScaffolded => Self::Unsolved,
+            Exit::BuildFailed => Self::BuildFailed,
+            Exit::TimedOut => Self::TimedOut,
+            _ if any_line(&|l| l.contains("✘ wrong answer")) => Self::WrongAnswer,
+            _ if any_line(&|l| l.starts_with("Part") && l.contains("✖") && l.contains("error")) => {
+                Self::Errored
+            }
+            Exit::Failure => Self::Panicked,
+            Exit::Success if any_line(&|l| l.starts_with("Part") && l.contains('✖')) => {
+                Self::Unsolved
+            }
+            Exit::Success => Self::Solved,
+        }
+    }
+
+    fn is_failure(self) -> bool {
+        !matches!(self, Self::Solved | Self::Unsolved)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Solved => "Solved",
+            Self::Unsolved => "Unsolved",
+            Self::WrongAnswer => "Wrong answer",
+            Self::Errored => "Errored",
+            Self::Panicked => "Panicked",
+            Self::TimedOut => "Timed out",
+            Self::BuildFailed => "Build failed",
+        }
+    }
+}
+
+/// Strips terminal escapes and the intermediate results that the runner overwrites with `\r` from a line of output.
+fn clean_line(line: &str) -> String {
+    line.rsplit('\r')
+        .next()
+        .unwrap_or(line)
+        .replace(ANSI_BOLD, "")
+        .replace(ANSI_ITALIC, "")
+        .replace(ANSI_RESET, "")
+}
+
+/// Extracts the panic message, without backtrace or backtrace hint, from a solution's stderr.
+fn panic_message(stderr: &[String]) -> String {
+    let start = stderr
+        .iter()
+        .position(|line| line.contains("panicked at"))
+        .unwrap_or(0);
+
+    stderr[start..]
+        .iter()
+        .take_while(|line| !line.starts_with("stack backtrace:"))
+        .filter(|line| !line.starts_with("note: "))
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Turns the output of a solution run into one test case per selected part.
+fn test_cases(
+    day: Day,
+    output: &child_commands::Output,
+    timings: &Timings,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+) -> Vec<TestCase> {
+    let lines = output
+        .stdout
+        .iter()
+        .map(|line| clean_line(line))
+        .collect::<Vec<_>>();
+
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    let position = |part: u8| {
+        let prefix = format!("Part {part}:");
+        lines.iter().position(|line| line.starts_with(&prefix))
+    };
+    // a panic ends the run, so it is attributed to the first part that did not report a result.
+    let panicked_part = parts.iter().copied().find(|part| position(*part).is_none());
+
+    parts
+        .iter()
+        .map(|&part| {
+            let duration = match part {
+                1 => timings.part_1.as_deref(),
+                _ => timings.part_2.as_deref(),
+            }
+            .and_then(readme_benchmarks::parse_duration_nanos)
+            .map(|nanos| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Duration::from_nanos(nanos as u64)
+            });
+
+            let outcome = match (output.exit, position(part)) {
+                (Exit::NotScaffolded, _) => Outcome::Skipped("not scaffolded".into()),
+                (Exit::BuildFailed, _) => Outcome::Failed {
+                    message: "failed to build".into(),
+                    body: output.stderr.join("\n"),
+                },
+                (Exit::TimedOut, None) => Outcome::Failed {
+                    message: format!(
+                        "timed out after {:.1?}",
+                        timeout.expect("only runs with a timeout time out")
+                    ),
+                    body: String::new(),
+                },
+                (Exit::Failure, None) if panicked_part == Some(part) => Outcome::Failed {
+                    message: "panicked".into(),
+                    body: panic_message(&output.stderr),
+                },
+                (_, None) => Outcome::Skipped("not run".into()),
+                (_, Some(index)) => {
+                    let details = lines[index + 1..]
+                        .iter()
+                        .take_while(|line| !line.starts_with("Part "))
+                        .map(|line| line.trim_end())
+                        .collect::<Vec<_>>();
+                    let wrong_answer = details
+                        .iter()
+                        .position(|line| line.trim_start().starts_with("✘ wrong answer"));
+
+                    if lines[index].contains("✖ error") {
+                        Outcome::Failed {
+                            message: "error".into(),
+                            body: details
+                                .iter()
+                                .map(|line| line.trim())
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        }
+                    } else if lines[index].contains('✖') {
+                        Outcome::Skipped("unsolved".into())
+                    } else if let Some(wrong_answer) = wrong_answer {
+                        Outcome::Failed {
+                            message: details[wrong_answer]
+                                .trim()
+                                .trim_start_matches('✘')
+                                .trim_start()
+                                .trim_end_matches(':')
+                                .to_string(),
+                            body: details[wrong_answer + 1..]
+                                .join("\n")
+                                .trim_end()
+                                .to_string(),
+                        }
+                    } else {
+                        Outcome::Passed
+                    }
+                }
+            };
+
+            TestCase {
+                day,
+                part,
+                duration,
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Runs the selected solutions and exits with a non-zero status if any of them failed.
+/// If `reports` are passed, the results of each part are written to them once all days ran.
+pub fn handle(
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    filter: &Filter,
+    reports: &[Report],
+) {
+    let mut timings: Vec<Timings> = vec![];
+    let mut statuses: Vec<(Day, DayStatus)> = vec![];
+    let mut cases: Vec<TestCase> = vec![];
+    let days = filter.select_days();
+
+    let mut record_output = |day: Day, output: &child_commands::Output| {
+        let status = DayStatus::from_output(output);
+        let timing = child_commands::parse_exec_time(&output.stdout, day);
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if !reports.is_empty() {
+            cases.extend(test_cases(day, output, &timing, filter.part, timeout));
+        }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        if !output.stdout.is_empty() {
+            timings.push(timing);
         }
-    });
+
+        statuses.push((day, status));
+        status
+    };
+
+    // timed runs stay sequential so that solutions do not compete for cpu time.
+    if jobs > 1 && is_timed {
+        eprintln!("Timed runs are always sequential, ignoring `--jobs {jobs}`.");
+    }
+
+    if jobs > 1 && !is_timed {
+        run_parallel(
+            days,
+            is_release,
+            jobs,
+            filter.part,
+            timeout,
+            |day, output| {
+                print_day_header(day, days);
+                output.print();
+                print_exit(&output, timeout);
+                record_output(day, &output);
+            },
+        );
+    } else if is_timed && io::stdout().is_terminal() {
+        run_with_progress(days, is_release, filter.part, timeout, |day, output| {
+            record_output(day, output)
+        });
+    } else {
+        days.iter().for_each(|day| {
+            print_day_header(day, days);
+            let output = child_commands::run_solution(
+                day,
+                is_timed,
+                is_release,
+                filter.part,
+                timeout,
+                true,
+                None,
+            )
+            .unwrap();
+            print_exit(&output, timeout);
+            record_output(day, &output);
+        });
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
+        if is_release && filter.is_restricted() {
+            println!("Not updating README with benchmarks of a filtered run.");
+        } else if is_release {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -41,6 +372,181 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    print_summary(&statuses);
+
+    for report in reports {
+        if let Err(err) = report.write(&cases) {
+            eprintln!("Failed to write {:?} report: {err}", report.format);
+        }
+    }
+
+    if statuses.iter().any(|(_, status)| status.is_failure()) {
+        process::exit(1);
+    }
+}
+
+/// Explains runs that ended without output of the solution.
+fn print_exit(output: &child_commands::Output, timeout: Option<Duration>) {
+    match output.exit {
+        Exit::NotScaffolded => println!("Not solved."),
+        Exit::BuildFailed => println!("Failed to build."),
+        Exit::TimedOut => println!(
+            "Timed out after {:.1?}.",
+            timeout.expect("only runs with a timeout time out")
+        ),
+        Exit::Success | Exit::Failure => {}
+    }
+}
+
+/// Summarizes a finished day in a single line of the progress view.
+fn progress_line(output: &child_commands::Output, status: DayStatus) -> String {
+    let symbol = match status {
+        DayStatus::Solved => "✔",
+        DayStatus::Unsolved => "·",
+        _ => "✘",
+    };
+
+    let mut parts = output
+        .stdout
+        .iter()
+        .map(|line| clean_line(line))
+        .filter(|line| line.starts_with("Part "))
+        .map(|line| line.trim_end().to_string())
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() || status.is_failure() {
+        parts.push(status.label().to_string());
+    }
+
+    format!("{symbol} {}", parts.join(" · "))
+}
+
+/// Runs solutions sequentially while showing a line per day that is updated as the solutions progress.
+/// The output of failed days is printed once all days ran.
+fn run_with_progress(
+    days: DaySet,
+    is_release: bool,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+    mut record: impl FnMut(Day, &child_commands::Output) -> DayStatus,
+) {
+    let scheduled = days
+        .iter()
+        .filter(|day| paths::bin_path(*day).exists())
+        .count();
+    let view = progress::View::start(&days.iter().collect::<Vec<_>>(), scheduled);
+    let mut failed = vec![];
+
+    for day in days.iter() {
+        view.started(day);
+
+        let output = child_commands::run_solution(
+            day,
+            true,
+            is_release,
+            part,
+            timeout,
+            false,
+            Some(Box::new(view.progress(day))),
+        )
+        .unwrap();
+
+        let status = record(day, &output);
+        view.finished(day, progress_line(&output, status));
+
+        if status.is_failure() {
+            failed.push((day, output));
+        }
+    }
+
+    view.finish();
+
+    for (day, output) in failed {
+        println!("\n{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+        output.print();
+        print_exit(&output, timeout);
+    }
+}
+
+fn print_summary(statuses: &[(Day, DayStatus)]) {
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+
+    for status in DayStatus::ALL {
+        let days = statuses
+            .iter()
+            .filter(|(_, x)| *x == status)
+            .map(|(day, _)| day.to_string())
+            .collect::<Vec<_>>();
+
+        if days.is_empty() && status.is_failure() {
+            continue;
+        }
+
+        println!(
+            "{:<13} {:>2}  {ANSI_ITALIC}{}{ANSI_RESET}",
+            format!("{}:", status.label()),
+            days.len(),
+            days.join(" ")
+        );
+    }
+}
+
+fn print_day_header(day: Day, days: DaySet) {
+    if days.iter().next() != Some(day) {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Runs up to `jobs` solutions concurrently.
+/// Each day's output is buffered and passed to `report` in day order, as soon as all previous days have been reported.
+fn run_parallel(
+    days: DaySet,
+    is_release: bool,
+    jobs: usize,
+    part: Option<u8>,
+    timeout: Option<Duration>,
+    mut report: impl FnMut(Day, child_commands::Output),
+) {
+    // build all solutions upfront, so that the concurrent runs do not wait on each other for the build lock.
+    child_commands::build_solutions(is_release);
+
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .expect("could not create thread pool");
+
+    let (sender, receiver) = mpsc::channel();
+
+    for day in days.iter() {
+        let sender = sender.clone();
+        pool.spawn(move || {
+            let output =
+                child_commands::run_solution(day, false, is_release, part, timeout, false, None)
+                    .unwrap();
+            // the receiver only hangs up once every day was reported.
+            let _ = sender.send((day, output));
+        });
+    }
+
+    drop(sender);
+
+    let mut pending = BTreeMap::new();
+    let mut next_days = days.iter().peekable();
+
+    for (day, output) in receiver {
+        pending.insert(day, output);
+
+        while let Some(output) = next_days.peek().and_then(|day| pending.remove(day)) {
+            let day = next_days.next().expect("peeked day exists");
+            report(day, output);
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -56,73 +562,224 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::Error;
+    use crate::template::progress::{self, Event};
+    use crate::template::{paths, readme_benchmarks};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// How a solution process ended.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Exit {
+        /// The day has not been scaffolded yet.
+        NotScaffolded,
+        BuildFailed,
+        Success,
+        Failure,
+        TimedOut,
+    }
+
+    /// The output of a solution run.
+    pub struct Output {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+        pub exit: Exit,
+    }
+
+    impl Output {
+        fn without_run(exit: Exit) -> Self {
+            Self {
+                stdout: vec![],
+                stderr: vec![],
+                exit,
+            }
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        /// Forwards buffered output to stdout/stderr.
+        pub fn print(&self) {
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
+            self.stdout.iter().for_each(|line| println!("{line}"));
+        }
+    }
+
+    fn get_build_args(day: Option<Day>, is_release: bool) -> Vec<String> {
+        let mut args = vec!["build".to_string(), "--quiet".to_string()];
+
+        match day {
+            Some(day) => {
+                args.push("--bin".to_string());
+                args.push(day.to_string());
+            }
+            None => args.push("--bins".to_string()),
+        }
 
         if is_release {
-            args.push("--release");
+            args.push("--release".to_string());
         }
 
+        args
+    }
+
+    fn get_args(is_timed: bool, part: Option<u8>) -> Vec<String> {
+        let mut args = vec![];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".to_string());
+        }
+
+        if let Some(part) = part {
+            args.push("--part".to_string());
+            args.push(part.to_string());
+        }
+
+        args
+    }
+
+    /// Build all solution bins, forwarding compiler output to stderr.
+    pub fn build_solutions(is_release: bool) {
+        // build failures surface again when the individual solutions are built.
+        let _ = Command::new("cargo")
+            .args(get_build_args(None, is_release))
+            .arg("--keep-going")
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status();
+    }
+
+    /// Waits for the child to exit, killing it once `timeout` has passed.
+    fn wait_with_timeout(cmd: &mut Child, timeout: Option<Duration>) -> Result<Exit, Error> {
+        let Some(timeout) = timeout else {
+            return Ok(if cmd.wait()?.success() {
+                Exit::Success
+            } else {
+                Exit::Failure
+            });
+        };
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(if status.success() {
+                    Exit::Success
+                } else {
+                    Exit::Failure
+                });
+            }
+
+            if Instant::now() >= deadline {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(Exit::TimedOut);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Build and run the solution bin for a given day.
+    /// Solutions are built with cargo, but run directly so that `timeout` only applies to the solution itself.
+    /// If `forward` is set, output is forwarded to stdout/stderr while it is grabbed, otherwise it is only buffered.
+    /// If `on_progress` is passed, the solution reports its progress to it instead of printing intermediate results.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        part: Option<u8>,
+        timeout: Option<Duration>,
+        forward: bool,
+        on_progress: Option<Box<dyn Fn(Event) + Send>>,
+    ) -> Result<Output, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !paths::bin_path(day).exists() {
+            return Ok(Output::without_run(Exit::NotScaffolded));
+        }
+
+        let build = Command::new("cargo")
+            .args(get_build_args(Some(day), is_release))
+            .stdout(Stdio::null())
+            .stderr(if forward {
+                Stdio::inherit()
+            } else {
+                Stdio::piped()
+            })
+            .output()?;
+
+        if !build.status.success() {
+            let mut output = Output::without_run(Exit::BuildFailed);
+            output.stderr = String::from_utf8_lossy(&build.stderr)
+                .lines()
+                .map(String::from)
+                .collect();
+            return Ok(output);
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = Command::new(paths::target_bin_path(day, is_release));
+        cmd.args(get_args(is_timed, part))
+            .current_dir(paths::project_root())
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        if on_progress.is_some() {
+            cmd.env(progress::ENV_VAR, "1");
+        }
+
+        let mut cmd = cmd.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .filter(|line| match (&on_progress, progress::parse(line)) {
+                    (Some(on_progress), Some(event)) => {
+                        on_progress(event);
+                        false
+                    }
+                    _ => true,
+                })
+                .inspect(|line| {
+                    if forward {
+                        eprintln!("{line}");
+                    }
+                })
+                .collect::<Vec<_>>()
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| {
+                    if forward {
+                        println!("{line}");
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let exit = wait_with_timeout(&mut cmd, timeout)?;
 
-        Ok(output)
+        Ok(Output {
+            stdout: stdout_thread.join().unwrap(),
+            stderr: stderr_thread.join().unwrap(),
+            exit,
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -136,7 +793,8 @@ mod child_commands {
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                // debug runs print the duration of a single run, timed runs the average of their samples.
+                if !l.starts_with("Part") || !l.trim_end().ends_with(')') {
                     return None;
                 }
 
@@ -161,27 +819,17 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
-            .split(" samples)")
-            .next()?
+            .trim_end()
+            .strip_suffix(')')?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = readme_benchmarks::parse_duration_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -236,6 +884,21 @@ mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn test_single_runs() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 > benching\rPart 1: 0 (2.5µs)".into(),
+                    "Part 2: ▼  (1.0ms)".into(),
+                    "(a multi-line answer)".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1002500_f64);
+            assert_eq!(res.part_1.unwrap(), "2.5µs");
+            assert_eq!(res.part_2.unwrap(), "1.0ms");
+        }
+
         #[test]
         fn test_missing_parts() {
             let res = parse_exec_time(
@@ -252,3 +915,109 @@ mod child_commands {
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::child_commands::{parse_exec_time, Exit, Output};
+    use super::{test_cases, DayStatus};
+    use crate::day;
+    use crate::template::test_report::Outcome;
+
+    fn output(stdout: &[&str], exit: Exit) -> Output {
+        Output {
+            stdout: stdout.iter().map(|x| x.to_string()).collect(),
+            stderr: vec![],
+            exit,
+        }
+    }
+
+    #[test]
+    fn classifies_solved_and_unsolved_days() {
+        let solved = output(&["Part 1: 1 (1.0ms)", "Part 2: 2 (1.0ms)"], Exit::Success);
+        assert_eq!(DayStatus::from_output(&solved), DayStatus::Solved);
+
+        let unsolved = output(&["Part 1: 1 (1.0ms)", "Part 2: ✖      "], Exit::Success);
+        assert_eq!(DayStatus::from_output(&unsolved), DayStatus::Unsolved);
+
+        let not_scaffolded = output(&[], Exit::NotScaffolded);
+        assert_eq!(DayStatus::from_output(&not_scaffolded), DayStatus::Unsolved);
+    }
+
+    #[test]
+    fn classifies_failed_days() {
+        let wrong = output(
+            &["Part 1: 1 (1.0ms)", "  ✘ wrong answer, expected 2"],
+            Exit::Failure,
+        );
+        assert_eq!(DayStatus::from_output(&wrong), DayStatus::WrongAnswer);
+
+        let errored = output(&["Part 1: ✖ error      ", "  bad input"], Exit::Failure);
+        assert_eq!(DayStatus::from_output(&errored), DayStatus::Errored);
+
+        let invalid_input = output(
+            &[
+                "Part 1: ✖ error",
+                "  invalid input: line 3: expected a card",
+            ],
+            Exit::Failure,
+        );
+        assert_eq!(DayStatus::from_output(&invalid_input), DayStatus::Errored);
+
+        let panicked = output(&["Part 1: 1 (1.0ms)"], Exit::Failure);
+        assert_eq!(DayStatus::from_output(&panicked), DayStatus::Panicked);
+
+        let timed_out = output(&[], Exit::TimedOut);
+        assert_eq!(DayStatus::from_output(&timed_out), DayStatus::TimedOut);
+    }
+
+    #[test]
+    fn extracts_test_cases() {
+        let mut wrong = output(
+            &[
+                "Part 1: \x1b[1m1\x1b[0m (1.0ms)",
+                "  ✘ wrong answer, expected \x1b[1m2\x1b[0m",
+                "Part 2: ✖ \x1b[1merror\x1b[0m      ",
+                "  bad input",
+                "  caused by: line 1",
+            ],
+            Exit::Failure,
+        );
+        let timings = parse_exec_time(&wrong.stdout, day!(1));
+        let cases = test_cases(day!(1), &wrong, &timings, None, None);
+
+        assert_eq!(cases[0].duration, Some(Duration::from_millis(1)));
+        assert_eq!(
+            cases[0].outcome,
+            Outcome::Failed {
+                message: "wrong answer, expected 2".into(),
+                body: String::new()
+            }
+        );
+        assert_eq!(
+            cases[1].outcome,
+            Outcome::Failed {
+                message: "error".into(),
+                body: "bad input\ncaused by: line 1".into()
+            }
+        );
+
+        wrong.stdout.truncate(2);
+        wrong.stderr = vec![
+            "thread 'main' panicked at src/bin/01.rs:1:1:".into(),
+            "oops".into(),
+            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace".into(),
+        ];
+        let cases = test_cases(day!(1), &wrong, &timings, Some(2), None);
+
+        assert_eq!(cases.len(), 1);
+        assert_eq!(
+            cases[0].outcome,
+            Outcome::Failed {
+                message: "panicked".into(),
+                body: "thread 'main' panicked at src/bin/01.rs:1:1:\noops".into()
+            }
+        );
+    }
+}
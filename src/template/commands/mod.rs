@@ -1,5 +1,7 @@
 pub mod all;
 pub mod download;
+pub mod profile;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod test;
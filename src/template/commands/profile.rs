@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::{paths, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// Cargo profile with release optimizations and debug symbols, see `Cargo.toml`.
+static PROFILE: &str = "profiling";
+
+/// Number of stacks printed in the summary.
+const TOP_STACKS: usize = 10;
+
+/// Number of innermost frames printed per stack in the summary.
+const SUMMARY_FRAMES: usize = 4;
+
+/// Builds a solution for profiling and runs a part in a loop under `perf`.
+/// If `perf` is not available, the commands to profile the solution are printed instead.
+pub fn handle(day: Day, part: Option<u8>, seconds: f64) {
+    build(day);
+
+    let bin = paths::target_profile_bin_path(day, PROFILE);
+    let mut run_args = vec![
+        "--profile".to_string(),
+        "--seconds".to_string(),
+        seconds.to_string(),
+    ];
+
+    if let Some(part) = part {
+        run_args.push("--part".to_string());
+        run_args.push(part.to_string());
+    }
+
+    let out_dir = paths::target_dir().join(PROFILE);
+    let data = out_dir.join(format!("{day}.perf.data"));
+    let folded = out_dir.join(format!("{day}.folded"));
+
+    let record_args = [
+        "record",
+        "-F",
+        "999",
+        "-g",
+        "--call-graph",
+        "fp",
+        "-o",
+        &data.to_string_lossy(),
+        "--",
+        &bin.to_string_lossy(),
+    ]
+    .map(String::from)
+    .into_iter()
+    .chain(run_args.iter().cloned())
+    .collect::<Vec<_>>();
+
+    if !has_perf() {
+        println!("`perf` was not found. To profile day {day}, run:");
+        println!();
+        println!("  perf {}", record_args.join(" "));
+        println!("  perf script -i {}", data.display());
+        println!();
+        println!(
+            "The binary can be used with other profilers as well: {} {}",
+            bin.display(),
+            run_args.join(" ")
+        );
+        return;
+    }
+
+    let status = Command::new("perf")
+        .args(&record_args)
+        .current_dir(paths::project_root())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        eprintln!("`perf record` failed.");
+        process::exit(status.code().unwrap_or(1));
+    }
+
+    let script = Command::new("perf")
+        .args(["script", "-i", &data.to_string_lossy()])
+        .stderr(Stdio::inherit())
+        .output()
+        .unwrap();
+
+    if !script.status.success() {
+        eprintln!("`perf script` failed.");
+        process::exit(script.status.code().unwrap_or(1));
+    }
+
+    let stacks = fold_stacks(&String::from_utf8_lossy(&script.stdout));
+    write_folded(&stacks, &folded);
+    print_summary(&stacks);
+
+    println!();
+    println!(
+        "Folded stacks were written to {}, e.g. for use with `inferno-flamegraph`.",
+        paths::display_path(&folded)
+    );
+}
+
+fn build(day: Day) {
+    // frame pointers let `perf` unwind stacks cheaply and reliably.
+    let rustflags = match env::var("RUSTFLAGS") {
+        Ok(flags) if !flags.is_empty() => format!("{flags} -C force-frame-pointers=yes"),
+        _ => "-C force-frame-pointers=yes".to_string(),
+    };
+
+    let status = Command::new("cargo")
+        .args([
+            "build",
+            "--quiet",
+            "--profile",
+            PROFILE,
+            "--bin",
+            &day.to_string(),
+        ])
+        .env("RUSTFLAGS", rustflags)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+fn has_perf() -> bool {
+    Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Folds the samples printed by `perf script` into `root;...;leaf` stacks and counts how often each was sampled.
+fn fold_stacks(script: &str) -> HashMap<String, usize> {
+    let mut stacks = HashMap::new();
+    let mut frames: Vec<&str> = vec![];
+
+    // a sample is a header line followed by indented frames, leaf first, and ends with a blank line.
+    for line in script.lines().chain([""]) {
+        if line.trim().is_empty() {
+            if !frames.is_empty() {
+                frames.reverse();
+                *stacks.entry(frames.join(";")).or_insert(0) += 1;
+                frames.clear();
+            }
+        } else if line.starts_with(char::is_whitespace) {
+            frames.push(parse_symbol(line));
+        }
+    }
+
+    stacks
+}
+
+/// Extracts the symbol from a frame line such as `55d0c0a1 _01::part_one+0x40 (/path/to/01)`.
+fn parse_symbol(line: &str) -> &str {
+    let symbol = line
+        .trim()
+        .split_once(' ')
+        .map_or("", |(_, rest)| rest)
+        .rsplit_once(" (")
+        .map_or(line.trim(), |(symbol, _)| symbol);
+
+    let symbol = match symbol.rsplit_once("+0x") {
+        Some((symbol, offset)) if offset.chars().all(|c| c.is_ascii_hexdigit()) => symbol,
+        _ => symbol,
+    };
+
+    if symbol.is_empty() {
+        "[unknown]"
+    } else {
+        symbol
+    }
+}
+
+fn sorted_stacks(stacks: &HashMap<String, usize>) -> Vec<(&String, &usize)> {
+    let mut sorted = stacks.iter().collect::<Vec<_>>();
+    sorted.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    sorted
+}
+
+fn write_folded(stacks: &HashMap<String, usize>, path: &Path) {
+    let content = sorted_stacks(stacks)
+        .iter()
+        .map(|(stack, count)| format!("{stack} {count}\n"))
+        .collect::<String>();
+
+    if let Err(err) = fs::write(path, content) {
+        eprintln!("Failed to write folded stacks: {err}");
+    }
+}
+
+fn print_summary(stacks: &HashMap<String, usize>) {
+    let total = stacks.values().sum::<usize>();
+
+    println!();
+    println!("{ANSI_BOLD}Hottest stacks{ANSI_RESET} ({total} samples)");
+    println!("--------------");
+
+    for (stack, count) in sorted_stacks(stacks).into_iter().take(TOP_STACKS) {
+        let frames = stack.split(';').collect::<Vec<_>>();
+        let inner = &frames[frames.len().saturating_sub(SUMMARY_FRAMES)..];
+        let ellipsis = if inner.len() < frames.len() {
+            "…;"
+        } else {
+            ""
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let share = *count as f64 / total as f64 * 100_f64;
+
+        println!(
+            "{share:>5.1}%  {ANSI_ITALIC}{ellipsis}{}{ANSI_RESET}",
+            inner.join(";")
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold_stacks, parse_symbol};
+
+    #[test]
+    fn parses_symbols() {
+        assert_eq!(
+            parse_symbol("\t    55d0c0a1 _01::part_one+0x40 (/root/target/profiling/01)"),
+            "_01::part_one"
+        );
+        assert_eq!(
+            parse_symbol("\t    55d0c0a1 core::str::<impl str>::lines+0x1f (/root/01)"),
+            "core::str::<impl str>::lines"
+        );
+        assert_eq!(
+            parse_symbol("\t    55d0c0a1 [unknown] ([unknown])"),
+            "[unknown]"
+        );
+    }
+
+    #[test]
+    fn folds_stacks() {
+        let script = "01 1234 10.000001:     1001001 cpu-clock:
+\t    2 _01::part_one+0x10 (/01)
+\t    1 main+0x5 (/01)
+
+01 1234 10.000002:     1001001 cpu-clock:
+\t    2 _01::part_one+0x12 (/01)
+\t    1 main+0x5 (/01)
+
+01 1234 10.000003:     1001001 cpu-clock:
+\t    3 core::str::lines+0x3 (/01)
+\t    2 _01::part_one+0x14 (/01)
+\t    1 main+0x5 (/01)
+";
+
+        let stacks = fold_stacks(script);
+        assert_eq!(stacks.len(), 2);
+        assert_eq!(stacks["main;_01::part_one"], 2);
+        assert_eq!(stacks["main;_01::part_one;core::str::lines"], 1);
+    }
+}
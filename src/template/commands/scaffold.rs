@@ -1,9 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::config::{self, Template};
+use crate::template::paths::{self, display_path};
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -22,31 +25,103 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples/part1", DAY));
-        assert_eq!(result, None);
+        for example in advent_of_code::template::read_examples("examples/part1", DAY) {
+            // examples without an answer are not checked, like in `cargo test-day`.
+            let Some(answer) = example.answer else { continue };
+            let result = part_one(&example.input).map(|x| x.to_string());
+            assert_eq!(result, Some(answer), "{}", example.name);
+        }
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples/part2", DAY));
-        assert_eq!(result, None);
+        for example in advent_of_code::template::read_examples("examples/part2", DAY) {
+            // examples without an answer are not checked, like in `cargo test-day`.
+            let Some(answer) = example.answer else { continue };
+            let result = part_two(&example.input).map(|x| x.to_string());
+            assert_eq!(result, Some(answer), "{}", example.name);
+        }
     }
 }
 "#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+const FALLIBLE_MODULE_TEMPLATE: &str = r#"use advent_of_code::template::answer::Unsolved;
+use anyhow::Result;
+
+advent_of_code::solution!(DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Result<u32> {
+    Err(Unsolved.into())
+}
+
+pub fn part_two(input: &str) -> Result<u32> {
+    Err(Unsolved.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::template::answer::PartResult;
+
+    #[test]
+    fn test_part_one() -> Result<()> {
+        for example in advent_of_code::template::read_examples("examples/part1", DAY) {
+            // examples without an answer are not checked, like in `cargo test-day`.
+            let Some(answer) = example.answer else { continue };
+            let result = part_one(&example.input).into_answer().into_result()?;
+            assert_eq!(result.map(|x| x.to_string()), Some(answer), "{}", example.name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_two() -> Result<()> {
+        for example in advent_of_code::template::read_examples("examples/part2", DAY) {
+            // examples without an answer are not checked, like in `cargo test-day`.
+            let Some(answer) = example.answer else { continue };
+            let result = part_two(&example.input).into_answer().into_result()?;
+            assert_eq!(result.map(|x| x.to_string()), Some(answer), "{}", example.name);
+        }
+        Ok(())
+    }
+}
+"#;
+
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path_part1: String = format!("data/examples/part1/{day}.txt");
-    let example_path_part2 = format!("data/examples/part2/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Day, fallible: bool) {
+    let input_path = paths::input_path(day);
+    let example_path_part1 = paths::example_path(1, day);
+    let example_path_part2 = paths::example_path(2, day);
+    let module_path = paths::bin_path(day);
+
+    // `--fallible` takes precedence over the template configured in `aoc.toml`.
+    let template = match (fallible, &config::get().scaffold.template) {
+        (true, _) | (false, Template::Fallible) => FALLIBLE_MODULE_TEMPLATE.to_string(),
+        (false, Template::Default) => MODULE_TEMPLATE.to_string(),
+        (false, Template::Custom(path)) => {
+            match fs::read_to_string(paths::project_root().join(path)) {
+                Ok(template) => template,
+                Err(e) => {
+                    eprintln!("Failed to read template \"{}\": {e}", path.display());
+                    process::exit(1);
+                }
+            }
+        }
+    };
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -57,12 +132,12 @@ pub fn handle(day: Day) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
+        template
             .replace("DAY_NUMBER", &day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", display_path(&module_path));
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -72,7 +147,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", display_path(&input_path));
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -82,7 +157,10 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path_part1) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path_part1);
+            println!(
+                "Created empty example file \"{}\"",
+                display_path(&example_path_part1)
+            );
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -92,7 +170,10 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path_part2) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path_part2);
+            println!(
+                "Created empty example file \"{}\"",
+                display_path(&example_path_part2)
+            );
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
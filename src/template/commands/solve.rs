@@ -1,8 +1,20 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
+use crate::template::logger::Verbosity;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// `viz` is [`None`] without a visualization, `Some(None)` to draw frames to the terminal and `Some(Some(dir))` to write them to `dir`.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    input: Option<String>,
+    example: Option<Option<u8>>,
+    verbosity: Verbosity,
+    viz: Option<Option<String>>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
@@ -20,6 +32,29 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    cmd_args.extend(verbosity.to_args());
+
+    match viz {
+        Some(Some(dir)) => {
+            cmd_args.push("--viz-dir".to_string());
+            cmd_args.push(dir);
+        }
+        Some(None) => cmd_args.push("--viz".to_string()),
+        None => {}
+    }
+
+    if let Some(example_part) = example {
+        cmd_args.push("--example".to_string());
+        if let Some(example_part) = example_part {
+            cmd_args.push(example_part.to_string());
+        }
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -27,5 +62,9 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,39 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::logger::Verbosity;
+use crate::Day;
+
+pub fn handle(day: Day, release: bool, part: Option<u8>, verbosity: Verbosity) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.push("--test".to_string());
+    cmd_args.extend(verbosity.to_args());
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
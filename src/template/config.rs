@@ -0,0 +1,298 @@
+/// Module that reads project-wide settings from `aoc.toml` in the project root.
+/// Every setting is optional, environment variables and command-line flags take precedence over the file:
+///  1. `year` is overridden by `AOC_YEAR`.
+///  2. `paths.data` is overridden by `AOC_DATA_DIR`.
+///  3. `paths.session_file` is ignored if aoc-cli's `ADVENT_OF_CODE_SESSION` is set.
+///  4. `scaffold.template` is overridden by `scaffold --fallible`.
+///  5. `all.timeout_secs` and `all.jobs` are overridden by `all --timeout` and `all --jobs`.
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fmt, fs, io, process};
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+use crate::template::paths;
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub year: Option<u16>,
+    pub paths: Paths,
+    pub scaffold: Scaffold,
+    pub bench: Bench,
+    pub all: All,
+    pub submit: Submit,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    /// Directory with inputs, examples, puzzles and answers, relative to the project root.
+    pub data: Option<PathBuf>,
+    /// Session cookie file passed to aoc-cli.
+    pub session_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scaffold {
+    /// `default`, `fallible` or the path of a custom template, relative to the project root.
+    pub template: Template,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum Template {
+    #[default]
+    Default,
+    Fallible,
+    Custom(PathBuf),
+}
+
+impl From<String> for Template {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "default" => Self::Default,
+            "fallible" => Self::Fallible,
+            path => Self::Custom(path.into()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bench {
+    /// Approximate time spent benching a part, samples are clamped to `min_samples..=max_samples`.
+    pub budget_ms: u64,
+    pub min_samples: u64,
+    pub max_samples: u64,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            budget_ms: 1000,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl Bench {
+    #[must_use]
+    pub fn budget(&self) -> Duration {
+        Duration::from_millis(self.budget_ms)
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct All {
+    pub timeout_secs: Option<f64>,
+    pub jobs: usize,
+}
+
+impl Default for All {
+    fn default() -> Self {
+        Self {
+            timeout_secs: None,
+            jobs: 1,
+        }
+    }
+}
+
+impl All {
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs_f64)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Submit {
+    pub policy: SubmitPolicy,
+}
+
+/// Whether `--submit` submits right away, asks first, or is refused.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmitPolicy {
+    #[default]
+    Allow,
+    Confirm,
+    Never,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    Parser(toml::de::Error),
+    /// A setting that parsed, but has a value that can not be used.
+    Invalid(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IO(err) => write!(f, "{err}"),
+            Error::Parser(err) => write!(f, "{err}"),
+            Error::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `aoc.toml`, a missing file yields the default settings.
+    pub fn read() -> Result<Self, Error> {
+        match fs::read_to_string(paths::config_path()) {
+            Ok(content) => Self::parse(&content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(Error::IO(err)),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let config: Self = toml::from_str(content).map_err(Error::Parser)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Rejects values that would make benches or `all` fail at runtime.
+    fn validate(&self) -> Result<(), Error> {
+        let bench = &self.bench;
+
+        if bench.min_samples == 0 {
+            return Err(Error::Invalid(
+                "bench.min_samples must be at least 1".into(),
+            ));
+        }
+        if bench.min_samples > bench.max_samples {
+            return Err(Error::Invalid(format!(
+                "bench.min_samples ({}) must not be greater than bench.max_samples ({})",
+                bench.min_samples, bench.max_samples
+            )));
+        }
+        if let Some(timeout) = self.all.timeout_secs {
+            if !(timeout > 0.0 && timeout.is_finite()) {
+                return Err(Error::Invalid(format!(
+                    "all.timeout_secs must be a positive number of seconds, found {timeout}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The puzzle year, `AOC_YEAR` takes precedence over the file.
+    #[must_use]
+    pub fn year(&self) -> Option<u16> {
+        match env::var("AOC_YEAR") {
+            Ok(year) => year.parse().ok(),
+            Err(_) => self.year,
+        }
+    }
+}
+
+static CONFIG: Lazy<Config> = Lazy::new(|| match Config::read() {
+    Ok(config) => config,
+    Err(err) => {
+        eprintln!("Failed to read aoc.toml: {err}");
+        process::exit(1);
+    }
+});
+
+/// The settings of this project, read once per process.
+#[must_use]
+pub fn get() -> &'static Config {
+    &CONFIG
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{Config, Error, SubmitPolicy, Template};
+
+    #[test]
+    fn defaults_to_empty_config() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.bench.budget(), Duration::from_secs(1));
+        assert_eq!(config.all.jobs, 1);
+        assert_eq!(config.all.timeout(), None);
+    }
+
+    #[test]
+    fn parses_all_settings() {
+        let config = Config::parse(
+            r#"
+year = 2022
+
+[paths]
+data = "puzzles"
+session_file = "/home/me/.adventofcode.session"
+
+[scaffold]
+template = "templates/day.rs"
+
+[bench]
+budget_ms = 250
+min_samples = 5
+max_samples = 500
+
+[all]
+timeout_secs = 2.5
+jobs = 4
+
+[submit]
+policy = "confirm"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.paths.data, Some(PathBuf::from("puzzles")));
+        assert_eq!(
+            config.scaffold.template,
+            Template::Custom("templates/day.rs".into())
+        );
+        assert_eq!(config.bench.budget(), Duration::from_millis(250));
+        assert_eq!(config.bench.max_samples, 500);
+        assert_eq!(config.all.timeout(), Some(Duration::from_millis(2500)));
+        assert_eq!(config.all.jobs, 4);
+        assert_eq!(config.submit.policy, SubmitPolicy::Confirm);
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        assert!(Config::parse("[bench]\nbudget = 1").is_err());
+        assert!(Config::parse("[submit]\npolicy = \"sometimes\"").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        for content in [
+            "[bench]\nmin_samples = 20\nmax_samples = 10",
+            "[bench]\nmin_samples = 0",
+            "[bench]\nmin_samples = 0\nmax_samples = 0",
+            "[all]\ntimeout_secs = -1.0",
+            "[all]\ntimeout_secs = 0.0",
+        ] {
+            assert!(
+                matches!(Config::parse(content), Err(Error::Invalid(_))),
+                "{content}"
+            );
+        }
+
+        assert_eq!(
+            Config::parse("[bench]\nmin_samples = 20\nmax_samples = 10")
+                .unwrap_err()
+                .to_string(),
+            "bench.min_samples (20) must not be greater than bench.max_samples (10)"
+        );
+        assert!(Config::parse("[bench]\nmin_samples = 1\nmax_samples = 1").is_ok());
+    }
+}
@@ -0,0 +1,132 @@
+/// Discovery of example files, of which every part of a day can have several.
+///
+/// Examples for a day live in `data/<folder>/` and may be named either
+///  1. `05.txt` (the default example),
+///  2. `05-<name>.txt`, e.g. `05-a.txt`, or
+///  3. `05/<name>.txt`, e.g. `05/1.txt`.
+///
+/// The expected answer of an example is stored next to it with an `.answer` extension, e.g. `05-a.answer`.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::get_data_folder;
+use crate::Day;
+
+/// An example input for one part of a day, along with its expected answer if one was recorded.
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// The example's path relative to the data folder, e.g. `examples/part1/05-a.txt`.
+    pub name: String,
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+/// Reads every example for a day from `data/<folder>/`, starting with the default `<day>.txt` example.
+#[must_use]
+pub fn read_examples(folder: &str, day: Day) -> Vec<Example> {
+    let folder_path = get_data_folder(folder);
+    let day = day.to_string();
+
+    let mut paths = list_files(&folder_path)
+        .into_iter()
+        .filter(|path| is_example_file(path, &day))
+        .chain(
+            list_files(&folder_path.join(&day))
+                .into_iter()
+                .filter(|path| has_extension(path, "txt")),
+        )
+        .collect::<Vec<_>>();
+
+    // the default example comes first, the rest in file name order.
+    paths.sort_by_key(|path| (path.file_stem() != Some(day.as_ref()), path.clone()));
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let relative = path.strip_prefix(&folder_path).unwrap_or(&path);
+
+            Example {
+                name: format!("{folder}/{}", relative.display()),
+                input: fs::read_to_string(&path).expect("could not open example file"),
+                answer: read_answer(&path.with_extension("answer")),
+            }
+        })
+        .collect()
+}
+
+fn list_files(folder: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().is_some_and(|ext| ext == extension)
+}
+
+fn is_example_file(path: &Path, day: &str) -> bool {
+    if !has_extension(path, "txt") {
+        return false;
+    }
+
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
+    };
+
+    stem == day
+        || stem
+            .strip_prefix(day)
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
+pub(crate) fn read_answer(path: &Path) -> Option<String> {
+    let answer = fs::read_to_string(path).ok()?;
+    Some(answer.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_example_file;
+    use std::path::Path;
+
+    #[test]
+    fn matches_example_file_names() {
+        assert!(is_example_file(
+            Path::new("data/examples/part1/05.txt"),
+            "05"
+        ));
+        assert!(is_example_file(
+            Path::new("data/examples/part1/05-a.txt"),
+            "05"
+        ));
+        assert!(is_example_file(
+            Path::new("data/examples/part1/05-larger.txt"),
+            "05"
+        ));
+    }
+
+    #[test]
+    fn ignores_other_files() {
+        assert!(!is_example_file(
+            Path::new("data/examples/part1/05.answer"),
+            "05"
+        ));
+        assert!(!is_example_file(
+            Path::new("data/examples/part1/15.txt"),
+            "05"
+        ));
+        assert!(!is_example_file(
+            Path::new("data/examples/part1/055.txt"),
+            "05"
+        ));
+        assert!(!is_example_file(
+            Path::new("data/examples/part1/.keep"),
+            "05"
+        ));
+    }
+}
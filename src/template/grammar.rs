@@ -0,0 +1,286 @@
+/// Declarative description of the structure of a puzzle input.
+/// `solution!` checks the input against a day's grammar before calling the parts, so that
+/// malformed or truncated inputs are reported with a line number instead of a panic deep inside a parser.
+use std::error::Error;
+use std::fmt::Display;
+
+use regex::Regex;
+
+struct LineRule {
+    regex: Regex,
+    description: String,
+    repeated: bool,
+}
+
+impl LineRule {
+    fn new(pattern: &str, description: &str, repeated: bool) -> Self {
+        Self {
+            regex: Regex::new(&format!("^(?:{pattern})$")).expect("valid grammar pattern"),
+            description: description.into(),
+            repeated,
+        }
+    }
+}
+
+/// A block of consecutive lines, separated from other sections by a blank line.
+#[derive(Default)]
+pub struct Section {
+    rules: Vec<LineRule>,
+}
+
+impl Section {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects exactly one line matching `pattern`.
+    /// `description` is shown to the user when the line does not match.
+    #[must_use]
+    pub fn line(mut self, pattern: &str, description: &str) -> Self {
+        self.rules.push(LineRule::new(pattern, description, false));
+        self
+    }
+
+    /// Expects one or more lines matching `pattern`.
+    /// `description` is shown to the user when a line does not match.
+    #[must_use]
+    pub fn lines(mut self, pattern: &str, description: &str) -> Self {
+        self.rules.push(LineRule::new(pattern, description, true));
+        self
+    }
+
+    fn validate(&self, lines: &[(usize, &str)], end_line: usize) -> Result<(), GrammarError> {
+        let mut lines = lines.iter().peekable();
+
+        for rule in &self.rules {
+            let Some((line_nr, line)) = lines.next() else {
+                return Err(GrammarError::new(
+                    end_line,
+                    format!("unexpected end of section, expected `{}`", rule.description),
+                ));
+            };
+
+            if !rule.regex.is_match(line) {
+                return Err(GrammarError::expected(*line_nr, &rule.description));
+            }
+
+            if rule.repeated {
+                while lines
+                    .next_if(|(_, line)| rule.regex.is_match(line))
+                    .is_some()
+                {}
+            }
+        }
+
+        match (lines.next(), self.rules.last()) {
+            (None, _) => Ok(()),
+            (Some((line_nr, _)), Some(rule)) if rule.repeated => {
+                Err(GrammarError::expected(*line_nr, &rule.description))
+            }
+            (Some((line_nr, _)), _) => Err(GrammarError::new(
+                *line_nr,
+                "expected a blank line or the end of the input".into(),
+            )),
+        }
+    }
+}
+
+/// The expected structure of a puzzle input: a sequence of [`Section`]s separated by blank lines.
+///
+/// ```
+/// # use advent_of_code::template::grammar::{Grammar, Section};
+/// let grammar = Grammar::new()
+///     .section(Section::new().line(r"seeds:( \d+)+", "seeds: N N ..."))
+///     .repeated(
+///         Section::new()
+///             .line(r"\w+-to-\w+ map:", "<source>-to-<destination> map:")
+///             .lines(r"\d+ \d+ \d+", "N N N"),
+///     );
+///
+/// assert!(grammar.validate("seeds: 1 2\n\na-to-b map:\n1 2 3\n").is_ok());
+/// assert_eq!(
+///     grammar.validate("seeds: 1 2\n\na-to-b map:\n1 2\n").unwrap_err().to_string(),
+///     "line 4: expected `N N N`"
+/// );
+/// ```
+#[derive(Default)]
+pub struct Grammar {
+    sections: Vec<Section>,
+    repeat_last: bool,
+}
+
+impl Grammar {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A grammar for inputs consisting of a single section where every line matches `pattern`.
+    #[must_use]
+    pub fn lines(pattern: &str, description: &str) -> Self {
+        Self::new().section(Section::new().lines(pattern, description))
+    }
+
+    /// Expects the next section of the input to match `section`.
+    #[must_use]
+    pub fn section(mut self, section: Section) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Expects one or more further sections that all match `section`.
+    /// This has to be the last section of the grammar.
+    #[must_use]
+    pub fn repeated(mut self, section: Section) -> Self {
+        self.sections.push(section);
+        self.repeat_last = true;
+        self
+    }
+
+    /// Checks `input` against the grammar.
+    /// A single trailing newline is accepted, line numbers in errors start at 1.
+    pub fn validate(&self, input: &str) -> Result<(), GrammarError> {
+        let mut blocks: Vec<Vec<(usize, &str)>> = vec![vec![]];
+
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            let current = blocks.last_mut().expect("always at least one block");
+
+            if !line.is_empty() {
+                current.push((idx + 1, line));
+            } else if current.is_empty() {
+                return Err(GrammarError::new(idx + 1, "unexpected blank line".into()));
+            } else {
+                blocks.push(vec![]);
+            }
+        }
+
+        let end_line = input.lines().count() + 1;
+
+        if blocks.last().is_some_and(Vec::is_empty) {
+            blocks.pop();
+        }
+
+        for (idx, block) in blocks.iter().enumerate() {
+            let section = match self.sections.get(idx) {
+                Some(section) => section,
+                None if self.repeat_last => self.sections.last().expect("repeated section exists"),
+                None => {
+                    return Err(GrammarError::new(
+                        block[0].0,
+                        "expected the end of the input".into(),
+                    ))
+                }
+            };
+
+            let section_end = blocks.get(idx + 1).map_or(end_line, |next| next[0].0 - 1);
+
+            section.validate(block, section_end)?;
+        }
+
+        if let Some(section) = self.sections.get(blocks.len()) {
+            let description = section
+                .rules
+                .first()
+                .map_or("", |rule| rule.description.as_str());
+
+            return Err(GrammarError::new(
+                end_line,
+                format!("unexpected end of input, expected `{description}`"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// An error which is returned when an input does not match its [`Grammar`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct GrammarError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl GrammarError {
+    fn new(line: usize, message: String) -> Self {
+        Self { line, message }
+    }
+
+    fn expected(line: usize, description: &str) -> Self {
+        Self::new(line, format!("expected `{description}`"))
+    }
+}
+
+impl Error for GrammarError {}
+
+impl Display for GrammarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grammar, Section};
+
+    fn almanac_grammar() -> Grammar {
+        Grammar::new()
+            .section(Section::new().line(r"seeds:( \d+)+", "seeds: N N ..."))
+            .repeated(
+                Section::new()
+                    .line(r"\w+-to-\w+ map:", "<source>-to-<destination> map:")
+                    .lines(r"\d+ \d+ \d+", "N N N"),
+            )
+    }
+
+    #[test]
+    fn accepts_matching_lines() {
+        let grammar = Grammar::lines(r"Card +\d+:( +\d+)+ \|( +\d+)+", "Card N: ... | ...");
+        assert_eq!(
+            grammar.validate("Card 1: 1 2 | 3\nCard 2:  4 | 5 6\n"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reports_line_of_malformed_line() {
+        let grammar = Grammar::lines(r"Card +\d+:( +\d+)+ \|( +\d+)+", "Card N: ... | ...");
+        let err = grammar
+            .validate("Card 1: 1 2 | 3\nCard 2: 4 5\n")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected `Card N: ... | ...`");
+    }
+
+    #[test]
+    fn accepts_repeated_sections() {
+        let input = "seeds: 1 2\n\na-to-b map:\n1 2 3\n4 5 6\n\nb-to-c map:\n7 8 9\n";
+        assert_eq!(almanac_grammar().validate(input), Ok(()));
+    }
+
+    #[test]
+    fn reports_truncated_input() {
+        let err = almanac_grammar().validate("seeds: 1 2\n").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = almanac_grammar()
+            .validate("seeds: 1 2\n\na-to-b map:\n")
+            .unwrap_err();
+        assert_eq!(err.line, 4);
+    }
+
+    #[test]
+    fn reports_unexpected_blank_lines() {
+        let err = almanac_grammar()
+            .validate("seeds: 1 2\n\n\na-to-b map:\n1 2 3\n")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 3: unexpected blank line");
+    }
+
+    #[test]
+    fn reports_extra_sections() {
+        let grammar = Grammar::lines(r"\d+", "N");
+        let err = grammar.validate("1\n2\n\n3\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 4: expected the end of the input");
+    }
+}
@@ -0,0 +1,87 @@
+/// Selection of the input a solution binary runs against.
+/// By default this is the real puzzle input in `data/inputs/`, which can be swapped out via command-line flags:
+///  1. `--input <path>` reads the input from a file, `--input -` reads it from stdin.
+///  2. `--example` runs each part against its own example, `--example <part>` runs both parts against the example of that part.
+use std::io::{self, Read};
+use std::{env, fs, process};
+
+use super::read_file;
+use crate::Day;
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Inputs,
+    File(String),
+    Stdin,
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Reads the input source from the arguments passed to the solution binary.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        if let Some(input_index) = args.iter().position(|x| x == "--input") {
+            return match args.get(input_index + 1).map(String::as_str) {
+                Some("-") => Self::Stdin,
+                Some(path) => Self::File(path.into()),
+                None => {
+                    eprintln!(
+                        "Unexpected command-line input. Format: cargo solve 1 --input <path>"
+                    );
+                    process::exit(1);
+                }
+            };
+        }
+
+        if let Some(example_index) = args.iter().position(|x| x == "--example") {
+            return match args.get(example_index + 1).map(|x| x.parse::<u8>()) {
+                Some(Ok(part @ (1 | 2))) => Self::Example(Some(part)),
+                None => Self::Example(None),
+                Some(_) if args[example_index + 1].starts_with('-') => Self::Example(None),
+                Some(_) => {
+                    eprintln!(
+                        "Unexpected command-line input. Format: cargo solve 1 --example [1|2]"
+                    );
+                    process::exit(1);
+                }
+            };
+        }
+
+        Self::Inputs
+    }
+
+    /// Whether this is the real puzzle input, i.e. whether results may be submitted.
+    #[must_use]
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == Self::Inputs
+    }
+
+    /// Reads the inputs for part one and part two.
+    /// These only differ when each part runs against its own example.
+    #[must_use]
+    pub fn read(&self, day: Day) -> (String, String) {
+        let input = match self {
+            Self::Inputs => read_file("inputs", day),
+            Self::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+            Self::Example(Some(part)) => read_file(&format!("examples/part{part}"), day),
+            Self::Example(None) => {
+                return (
+                    read_file("examples/part1", day),
+                    read_file("examples/part2", day),
+                );
+            }
+        };
+
+        (input.clone(), input)
+    }
+}
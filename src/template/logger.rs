@@ -0,0 +1,226 @@
+/// Module that installs a logger for solutions, so they can use `log::debug!` & co. to dump intermediate state.
+/// The log level is controlled by `-v` / `-vv` / `-q` and `RUST_LOG`-style filters, e.g. `RUST_LOG=warn,05=trace`.
+use std::env;
+use std::io::Write;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// How much a solution logs, as passed via `-q`, `-v` or `-vv`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Logging is turned off, regardless of `RUST_LOG`.
+    Quiet,
+    /// `RUST_LOG` decides, only warnings and errors are logged if it is not set.
+    #[default]
+    Normal,
+    /// Logs up to `debug` for every target that is not configured otherwise in `RUST_LOG`.
+    Verbose,
+    /// Logs up to `trace` for every target that is not configured otherwise in `RUST_LOG`.
+    Trace,
+}
+
+impl Verbosity {
+    #[must_use]
+    pub fn new(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Self::Quiet,
+            (false, 0) => Self::Normal,
+            (false, 1) => Self::Verbose,
+            (false, _) => Self::Trace,
+        }
+    }
+
+    /// Parses the verbosity flags of the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args = env::args().skip(1).collect::<Vec<_>>();
+        let quiet = args.iter().any(|x| x == "-q" || x == "--quiet");
+        let verbose = args
+            .iter()
+            .map(|x| match x.as_str() {
+                "-v" | "--verbose" => 1,
+                "-vv" => 2,
+                _ => 0,
+            })
+            .sum();
+
+        Self::new(quiet, verbose)
+    }
+
+    /// The flags that pass this verbosity on to a solution.
+    #[must_use]
+    pub fn to_args(self) -> Vec<String> {
+        match self {
+            Self::Quiet => vec!["-q".into()],
+            Self::Normal => vec![],
+            Self::Verbose => vec!["-v".into()],
+            Self::Trace => vec!["-vv".into()],
+        }
+    }
+}
+
+/// A `RUST_LOG`-style filter: a comma separated list of `level` or `target=level` directives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: LevelFilter,
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    /// Parses a filter, invalid directives are reported on stderr and ignored.
+    #[must_use]
+    pub fn parse(spec: &str) -> Self {
+        let mut filter = Self {
+            default: LevelFilter::Warn,
+            directives: vec![],
+        };
+
+        for directive in spec.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => match level.parse() {
+                    Ok(level) => filter.directives.push((target.to_string(), level)),
+                    Err(_) => eprintln!("Ignoring invalid log directive \"{directive}\"."),
+                },
+                None => match directive.parse() {
+                    Ok(level) => filter.default = level,
+                    // a bare target enables all levels for it.
+                    Err(_) => filter
+                        .directives
+                        .push((directive.to_string(), LevelFilter::Trace)),
+                },
+            }
+        }
+
+        filter
+    }
+
+    /// Builds the filter from `RUST_LOG`, the verbosity flags override its default level.
+    #[must_use]
+    pub fn from_env(verbosity: Verbosity) -> Self {
+        let mut filter = Self::parse(&env::var("RUST_LOG").unwrap_or_default());
+
+        match verbosity {
+            Verbosity::Quiet => {
+                filter.default = LevelFilter::Off;
+                filter.directives.clear();
+            }
+            Verbosity::Normal => {}
+            Verbosity::Verbose => filter.default = LevelFilter::Debug,
+            Verbosity::Trace => filter.default = LevelFilter::Trace,
+        }
+
+        filter
+    }
+
+    /// The level of the most specific directive that matches `target`.
+    #[must_use]
+    pub fn level(&self, target: &str) -> LevelFilter {
+        self.directives
+            .iter()
+            .filter(|(name, _)| {
+                target == name
+                    || target
+                        .strip_prefix(name.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+struct Logger {
+    filter: Filter,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let _ = writeln!(
+                std::io::stderr().lock(),
+                "[{:<5} {}] {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger for the current process, configured by its arguments and `RUST_LOG`.
+pub fn init() {
+    let filter = Filter::from_env(Verbosity::from_args());
+    let max_level = filter.max_level();
+
+    if log::set_logger(Box::leak(Box::new(Logger { filter }))).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Turns logging off until the returned guard is dropped.
+#[must_use]
+pub fn pause() -> Paused {
+    let level = log::max_level();
+    log::set_max_level(LevelFilter::Off);
+    Paused(level)
+}
+
+/// Restores the previous log level when dropped, see [`pause`].
+pub struct Paused(LevelFilter);
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        log::set_max_level(self.0);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use log::LevelFilter;
+
+    use super::{Filter, Verbosity};
+
+    #[test]
+    fn parses_filters() {
+        let filter = Filter::parse("info,05=trace,advent_of_code::template=off,08");
+
+        assert_eq!(filter.level("01"), LevelFilter::Info);
+        assert_eq!(filter.level("05"), LevelFilter::Trace);
+        assert_eq!(filter.level("05::parse"), LevelFilter::Trace);
+        assert_eq!(filter.level("055"), LevelFilter::Info);
+        assert_eq!(filter.level("08"), LevelFilter::Trace);
+        assert_eq!(
+            filter.level("advent_of_code::template::runner"),
+            LevelFilter::Off
+        );
+        assert_eq!(filter.level("advent_of_code"), LevelFilter::Info);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn defaults_to_warnings() {
+        let filter = Filter::parse("");
+        assert_eq!(filter.level("01"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn maps_verbosity_flags() {
+        assert_eq!(Verbosity::new(false, 0), Verbosity::Normal);
+        assert_eq!(Verbosity::new(false, 1), Verbosity::Verbose);
+        assert_eq!(Verbosity::new(false, 3), Verbosity::Trace);
+        assert_eq!(Verbosity::new(true, 2), Verbosity::Quiet);
+    }
+}
@@ -1,36 +1,112 @@
 use crate::Day;
-use std::{env, fs};
+use std::fs;
+use std::path::PathBuf;
 
+pub mod answer;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod grammar;
+pub mod input;
+pub mod logger;
+pub mod paths;
+pub mod progress;
 pub mod readme_benchmarks;
+pub mod readme_stars;
 pub mod runner;
+pub mod test_report;
+pub mod visualize;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+pub use examples::{read_examples, Example};
+
+fn get_data_folder(folder: &str) -> PathBuf {
+    paths::data_dir().join(folder)
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = get_data_folder(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads the known correct answer for a part of the puzzle input from `data/answers/`.
+/// Returns [`None`] if no answer has been recorded for it yet.
+#[must_use]
+pub fn read_puzzle_answer(day: Day, part: u8) -> Option<String> {
+    examples::read_answer(&paths::answer_path(part, day))
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Optionally takes a [`grammar::Grammar`] that the input is validated against before the parts run:
+/// `solution!(4, grammar = input_grammar())`.
+///
+/// Optionally takes a `fn(&str, &mut FrameSink)` that renders the input of part one when run with `--viz` or `--viz-dir <dir>`,
+/// see [`visualize::FrameSink`]: `solution!(3, visualize = visualize)`. The parts do not run in that case.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr $(, grammar = $grammar:expr)? $(, visualize = $visualize:expr)? $(,)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            advent_of_code::template::logger::init();
+            if std::env::args().any(|x| x == "--test") {
+                let mut outcomes = test_part(part_one, DAY, 1);
+                outcomes.extend(test_part(part_two, DAY, 2));
+                let passed = print_test_report(&outcomes);
+                std::process::exit(if passed { 0 } else { 1 });
+            }
+            let (input_one, input_two) =
+                advent_of_code::template::input::InputSource::from_args().read(DAY);
+            $(
+                let grammar = $grammar;
+                let checked_one = grammar.validate(&input_one);
+                // both parts usually read the same input, which only needs to be checked once.
+                let checked_two = (input_two != input_one).then(|| grammar.validate(&input_two));
+                let checked_two = checked_two.as_ref().unwrap_or(&checked_one);
+                let mut is_valid = true;
+                for (part, checked) in [(1, &checked_one), (2, checked_two)] {
+                    if let Err(err) = checked {
+                        report_invalid_input(err, part);
+                        is_valid = false;
+                    }
+                }
+                if !is_valid {
+                    std::process::exit(1);
+                }
+            )?
+            if let Some(mut frames) = advent_of_code::template::visualize::FrameSink::from_args() {
+                let visualize: Option<fn(&str, &mut advent_of_code::template::visualize::FrameSink)> =
+                    None $(.or(Some($visualize)))?;
+                let Some(visualize) = visualize else {
+                    eprintln!("Day {DAY} has no visualization.");
+                    std::process::exit(1);
+                };
+                visualize(&input_one, &mut frames);
+                frames.finish();
+                return;
+            }
+            if std::env::args().any(|x| x == "--profile") {
+                profile_part(part_one, &input_one, 1);
+                profile_part(part_two, &input_two, 2);
+                return;
+            }
+            let statuses = [
+                run_part(part_one, &input_one, DAY, 1),
+                run_part(part_two, &input_two, DAY, 2),
+            ];
+            if statuses.iter().any(PartStatus::is_failure) {
+                std::process::exit(1);
+            }
         }
     };
 }
@@ -0,0 +1,120 @@
+/// Resolution of the paths the template reads from and writes to.
+/// Paths are resolved relative to the project root rather than the current working directory,
+/// so that binaries and tests work when run from a subdirectory or an IDE.
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::template::config;
+use crate::Day;
+
+/// Finds the project root, trying in order:
+///  1. the `CARGO_MANIFEST_DIR` that cargo sets when running binaries and tests.
+///  2. the closest ancestor of the current working directory that contains a `Cargo.toml`.
+///  3. the directory this crate was compiled from.
+#[must_use]
+pub fn project_root() -> PathBuf {
+    if let Some(dir) = env::var_os("CARGO_MANIFEST_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| {
+            cwd.ancestors()
+                .find(|dir| dir.join("Cargo.toml").is_file())
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+}
+
+/// The data directory, which can be set via `paths.data` in `aoc.toml` or the `AOC_DATA_DIR` environment variable.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    match env::var_os("AOC_DATA_DIR") {
+        Some(dir) => project_root().join(dir),
+        None => match &config::get().paths.data {
+            Some(dir) => project_root().join(dir),
+            None => project_root().join("data"),
+        },
+    }
+}
+
+/// The project-wide settings, see [`config`].
+#[must_use]
+pub fn config_path() -> PathBuf {
+    project_root().join("aoc.toml")
+}
+
+#[must_use]
+pub fn input_path(day: Day) -> PathBuf {
+    data_dir().join("inputs").join(format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn puzzle_path(day: Day) -> PathBuf {
+    data_dir().join("puzzles").join(format!("{day}.md"))
+}
+
+#[must_use]
+pub fn example_path(part: u8, day: Day) -> PathBuf {
+    data_dir()
+        .join("examples")
+        .join(format!("part{part}"))
+        .join(format!("{day}.txt"))
+}
+
+/// The known correct answer for a part of the puzzle input.
+#[must_use]
+pub fn answer_path(part: u8, day: Day) -> PathBuf {
+    data_dir()
+        .join("answers")
+        .join(format!("part{part}"))
+        .join(format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn bin_path(day: Day) -> PathBuf {
+    project_root()
+        .join("src")
+        .join("bin")
+        .join(format!("{day}.rs"))
+}
+
+/// The directory cargo builds into, which respects the `CARGO_TARGET_DIR` environment variable.
+#[must_use]
+pub fn target_dir() -> PathBuf {
+    match env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => project_root().join(dir),
+        None => project_root().join("target"),
+    }
+}
+
+/// The compiled solution binary of a debug or release build.
+#[must_use]
+pub fn target_bin_path(day: Day, is_release: bool) -> PathBuf {
+    let profile = if is_release { "release" } else { "debug" };
+    target_profile_bin_path(day, profile)
+}
+
+/// The compiled solution binary of a build with a custom cargo profile.
+#[must_use]
+pub fn target_profile_bin_path(day: Day, profile: &str) -> PathBuf {
+    target_dir()
+        .join(profile)
+        .join(format!("{day}{}", env::consts::EXE_SUFFIX))
+}
+
+#[must_use]
+pub fn readme_path() -> PathBuf {
+    project_root().join("README.md")
+}
+
+/// Formats a path for output, relative to the project root if it is located inside of it.
+#[must_use]
+pub fn display_path(path: &Path) -> String {
+    let root = project_root();
+    path.strip_prefix(&root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
@@ -0,0 +1,283 @@
+/// Module that displays the progress of a timed `all` run, with one line per day.
+/// Solutions report their progress to `all` through marker lines on stderr, see [`report`].
+use std::collections::BTreeMap;
+use std::io::{stdout, Write};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use std::{env, fmt};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// Set on solution processes whose progress is displayed.
+pub const ENV_VAR: &str = "AOC_PROGRESS";
+
+static MARKER: &str = "::progress::";
+
+static SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+const TICK: Duration = Duration::from_millis(80);
+
+/// What a solution process is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Running {
+        part: u8,
+    },
+    Benching {
+        part: u8,
+        samples: u128,
+        total: u128,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running { part } => write!(f, "{MARKER} part={part} running"),
+            Self::Benching {
+                part,
+                samples,
+                total,
+            } => write!(f, "{MARKER} part={part} samples={samples}/{total}"),
+        }
+    }
+}
+
+/// Returns `true` if the solution was started by a progress view.
+#[must_use]
+pub fn is_enabled() -> bool {
+    env::var_os(ENV_VAR).is_some()
+}
+
+/// Reports an event to the progress view that started this solution, if there is one.
+pub fn report(event: Event) {
+    if is_enabled() {
+        eprintln!("{event}");
+    }
+}
+
+/// Parses an event reported by a solution, returns [`None`] for all other lines.
+#[must_use]
+pub fn parse(line: &str) -> Option<Event> {
+    let rest = line.strip_prefix(MARKER)?.trim();
+    let (part, state) = rest.strip_prefix("part=")?.split_once(' ')?;
+    let part = part.parse().ok()?;
+
+    if state == "running" {
+        return Some(Event::Running { part });
+    }
+
+    let (samples, total) = state.strip_prefix("samples=")?.split_once('/')?;
+
+    Some(Event::Benching {
+        part,
+        samples: samples.parse().ok()?,
+        total: total.parse().ok()?,
+    })
+}
+
+enum Message {
+    Started(Day),
+    Progress(Day, Event),
+    Finished(Day, String),
+}
+
+enum DayState {
+    Pending,
+    Started,
+    Progress(Event),
+    Finished(String),
+}
+
+/// Handle to a progress view that redraws itself in a background thread until it is finished.
+pub struct View {
+    sender: Sender<Message>,
+    thread: JoinHandle<()>,
+}
+
+impl View {
+    /// Starts drawing a line for each of `days`, `scheduled` is the number of days that will actually run a solution.
+    #[must_use]
+    pub fn start(days: &[Day], scheduled: usize) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let days = days.iter().map(|day| (*day, DayState::Pending)).collect();
+
+        let thread = thread::spawn(move || {
+            let mut state = State {
+                days,
+                scheduled,
+                started_at: Instant::now(),
+                finished_runs: 0,
+                drawn_lines: 0,
+            };
+
+            loop {
+                match receiver.recv_timeout(TICK) {
+                    Ok(message) => state.update(message),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                state.draw();
+            }
+
+            state.draw();
+        });
+
+        Self { sender, thread }
+    }
+
+    pub fn started(&self, day: Day) {
+        let _ = self.sender.send(Message::Started(day));
+    }
+
+    /// Returns a callback that forwards the events of a day's solution to the view.
+    pub fn progress(&self, day: Day) -> impl Fn(Event) + Send + 'static {
+        let sender = self.sender.clone();
+        move |event| {
+            let _ = sender.send(Message::Progress(day, event));
+        }
+    }
+
+    /// Marks a day as finished, `summary` replaces its progress line.
+    pub fn finished(&self, day: Day, summary: String) {
+        let _ = self.sender.send(Message::Finished(day, summary));
+    }
+
+    /// Draws the final state of all days and returns once it is on screen.
+    pub fn finish(self) {
+        drop(self.sender);
+        let _ = self.thread.join();
+    }
+}
+
+struct State {
+    days: BTreeMap<Day, DayState>,
+    scheduled: usize,
+    started_at: Instant,
+    /// Number of finished days that ran a solution.
+    finished_runs: usize,
+    drawn_lines: usize,
+}
+
+impl State {
+    fn update(&mut self, message: Message) {
+        let (day, state) = match message {
+            Message::Started(day) => (day, DayState::Started),
+            Message::Progress(day, event) => (day, DayState::Progress(event)),
+            Message::Finished(day, summary) => (day, DayState::Finished(summary)),
+        };
+
+        let previous = self.days.insert(day, state);
+
+        if matches!(self.days.get(&day), Some(DayState::Finished(_)))
+            && matches!(previous, Some(DayState::Progress(_)))
+        {
+            self.finished_runs += 1;
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let frame = self.started_at.elapsed().as_millis() / TICK.as_millis();
+        #[allow(clippy::cast_possible_truncation)]
+        let spinner = SPINNER[frame as usize % SPINNER.len()];
+
+        let mut lines = self
+            .days
+            .iter()
+            .map(|(day, state)| {
+                let status = match state {
+                    DayState::Pending => format!("{ANSI_ITALIC}waiting{ANSI_RESET}"),
+                    DayState::Started => format!("{spinner} {ANSI_ITALIC}starting{ANSI_RESET}"),
+                    DayState::Progress(Event::Running { part }) => {
+                        format!("{spinner} Part {part} {ANSI_ITALIC}running{ANSI_RESET}")
+                    }
+                    DayState::Progress(Event::Benching {
+                        part,
+                        samples,
+                        total,
+                    }) => format!(
+                        "{spinner} Part {part} {ANSI_ITALIC}benching{ANSI_RESET} {samples}/{total} samples"
+                    ),
+                    DayState::Finished(summary) => summary.clone(),
+                };
+
+                format!("{ANSI_BOLD}Day {day}{ANSI_RESET}  {status}")
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(String::new());
+        lines.push(self.footer());
+        lines
+    }
+
+    /// Estimates the total duration from the average duration of the days that ran so far.
+    fn footer(&self) -> String {
+        let elapsed = self.started_at.elapsed();
+        let finished = self
+            .days
+            .values()
+            .filter(|x| matches!(x, DayState::Finished(_)))
+            .count();
+
+        let estimate = if finished == self.days.len() {
+            format!("{elapsed:.1?}")
+        } else if self.finished_runs == 0 {
+            "…".to_string()
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            let estimate = elapsed * self.scheduled as u32 / self.finished_runs as u32;
+            format!("{estimate:.1?}")
+        };
+
+        format!(
+            "{ANSI_BOLD}Elapsed:{ANSI_RESET} {ANSI_ITALIC}{elapsed:.1?}{ANSI_RESET}, {ANSI_BOLD}estimated total:{ANSI_RESET} {ANSI_ITALIC}{estimate}{ANSI_RESET}"
+        )
+    }
+
+    /// Moves the cursor back to the first line of the view and overwrites all lines.
+    fn draw(&mut self) {
+        let lines = self.lines();
+        let mut stdout = stdout().lock();
+
+        if self.drawn_lines > 0 {
+            let _ = write!(stdout, "\x1b[{}A", self.drawn_lines);
+        }
+
+        for line in &lines {
+            let _ = writeln!(stdout, "\r\x1b[2K{line}");
+        }
+
+        let _ = stdout.flush();
+        self.drawn_lines = lines.len();
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Event};
+
+    #[test]
+    fn parses_reported_events() {
+        let events = [
+            Event::Running { part: 1 },
+            Event::Benching {
+                part: 2,
+                samples: 12,
+                total: 10000,
+            },
+        ];
+
+        for event in events {
+            assert_eq!(parse(&event.to_string()), Some(event));
+        }
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(parse("thread 'main' panicked at src/bin/01.rs:1:1:"), None);
+        assert_eq!(parse("::progress:: part=1 samples=x/10"), None);
+    }
+}
@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::paths;
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -36,6 +37,22 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parses a duration as formatted by `{:.1?}` (e.g. `74.13ms`) into nanoseconds.
+#[must_use]
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -94,9 +111,53 @@ fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> R
     Ok(())
 }
 
+fn parse_row(row: &str) -> Option<Timings> {
+    let cells = row.split('|').map(str::trim).collect::<Vec<_>>();
+    let [_, day, part_1, part_2, ..] = cells.as_slice() else {
+        return None;
+    };
+
+    let day = day
+        .strip_prefix("[Day ")?
+        .split(']')
+        .next()?
+        .parse::<u8>()
+        .ok()
+        .and_then(Day::new)?;
+
+    let parse_part = |cell: &str| {
+        let timing = cell.trim_matches('`');
+        parse_duration_nanos(timing).map(|nanos| (timing.to_string(), nanos))
+    };
+
+    let part_1 = parse_part(part_1);
+    let part_2 = parse_part(part_2);
+
+    Some(Timings {
+        day,
+        total_nanos: part_1.iter().chain(part_2.iter()).map(|x| x.1).sum(),
+        part_1: part_1.map(|x| x.0),
+        part_2: part_2.map(|x| x.0),
+    })
+}
+
+fn parse_content(s: &str) -> Result<Vec<Timings>, Error> {
+    let positions = locate_table(s)?;
+    Ok(s[positions.pos_start..positions.pos_end]
+        .lines()
+        .filter_map(parse_row)
+        .collect())
+}
+
+/// Reads the timings of the last benchmark run from the readme.
+pub fn read() -> Result<Vec<Timings>, Error> {
+    let readme = String::from_utf8_lossy(&fs::read(paths::readme_path())?).to_string();
+    parse_content(&readme)
+}
+
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let path = paths::readme_path();
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
     update_content(&mut readme, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
@@ -104,7 +165,7 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{parse_content, update_content, Timings, MARKER};
     use crate::day;
 
     fn get_mock_timings() -> Vec<Timings> {
@@ -183,4 +244,15 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn reads_benchmarks() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let timings = parse_content(&s).unwrap();
+        assert_eq!(timings.len(), 3);
+        assert_eq!(timings[2].day, day!(4));
+        assert_eq!(timings[2].part_1.as_deref(), Some("40ms"));
+        assert_eq!(timings[2].total_nanos, 9e+7);
+    }
 }
@@ -0,0 +1,114 @@
+/// Module that reads puzzle progress from the readme's stars table.
+/// The table is maintained by the `advent-readme-stars` workflow.
+use std::fs;
+
+use crate::template::paths;
+use crate::template::readme_benchmarks::Error;
+use crate::Day;
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The parts of a day that have been solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl Stars {
+    /// Whether the given part, or both parts if none is given, have been solved.
+    #[must_use]
+    pub fn is_solved(&self, part: Option<u8>) -> bool {
+        match part {
+            Some(1) => self.part_1,
+            Some(2) => self.part_2,
+            _ => self.part_1 && self.part_2,
+        }
+    }
+}
+
+fn parse_row(row: &str) -> Option<Stars> {
+    let cells = row.split('|').map(str::trim).collect::<Vec<_>>();
+    let [_, day, part_1, part_2, ..] = cells.as_slice() else {
+        return None;
+    };
+
+    let day = day
+        .strip_prefix("[Day ")?
+        .split(']')
+        .next()?
+        .parse::<u8>()
+        .ok()
+        .and_then(Day::new)?;
+
+    Some(Stars {
+        day,
+        part_1: part_1.contains('⭐'),
+        part_2: part_2.contains('⭐'),
+    })
+}
+
+fn parse_content(s: &str) -> Result<Vec<Stars>, Error> {
+    let matches: Vec<_> = s.match_indices(MARKER).collect();
+
+    let [start, end] = matches.as_slice() else {
+        return Err(Error::Parser(
+            "Could not find the stars table in README.".into(),
+        ));
+    };
+
+    Ok(s[start.0..end.0].lines().filter_map(parse_row).collect())
+}
+
+/// Reads the stars of every day listed in the readme.
+pub fn read() -> Result<Vec<Stars>, Error> {
+    let readme = String::from_utf8_lossy(&fs::read(paths::readme_path())?).to_string();
+    parse_content(&readme)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_content, Stars, MARKER};
+    use crate::day;
+
+    #[test]
+    fn reads_stars() {
+        let s = [
+            MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            MARKER,
+        ]
+        .join("\n");
+
+        let stars = parse_content(&s).unwrap();
+        assert_eq!(
+            stars,
+            vec![
+                Stars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: true
+                },
+                Stars {
+                    day: day!(2),
+                    part_1: true,
+                    part_2: false
+                },
+            ]
+        );
+        assert!(!stars[1].is_solved(None));
+        assert!(stars[1].is_solved(Some(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        parse_content("# readme").unwrap();
+    }
+}
@@ -1,33 +1,177 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answer::{Answer, PartResult};
+use crate::template::config::SubmitPolicy;
+use crate::template::input::InputSource;
+use crate::template::progress::{self, Event};
+use crate::template::{aoc_cli, read_examples, read_puzzle_answer, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{config, logger};
 use crate::Day;
 use std::fmt::Display;
-use std::io::{stdout, Write};
+use std::io::{self, stdout, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, hint, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+const DEFAULT_PROFILE_SECONDS: f64 = 5_f64;
+
+/// Number of runs between clock checks while profiling.
+const PROFILE_BATCH: u64 = 10;
+
+/// How a solution part fared when it was run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    Skipped,
+    Solved,
+    Unsolved,
+    WrongAnswer,
+    Error,
+}
+
+impl PartStatus {
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::WrongAnswer | Self::Error)
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+) -> PartStatus {
+    if !is_part_selected(part) {
+        return PartStatus::Skipped;
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    progress::report(Event::Running { part });
+
+    let (result, duration, samples) = run_timed(
+        |input| func(input).into_answer(),
+        input,
+        part,
+        |result| print_result(result, &part_str, ""),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match result {
+        Answer::Solved(result) => {
+            if !check_answer(&result, day, part) {
+                return PartStatus::WrongAnswer;
+            }
+            submit_result(result, day, part);
+            PartStatus::Solved
+        }
+        Answer::Unsolved => PartStatus::Unsolved,
+        Answer::Error(_) => PartStatus::Error,
     }
 }
 
+/// Reports input that does not match the day's grammar as an error of `part`, in the format that `all` counts as errored.
+pub fn report_invalid_input(err: &impl Display, part: u8) {
+    println!("Part {part}: ✖ {ANSI_BOLD}error{ANSI_RESET}");
+    println!("  invalid input: {err}");
+}
+
+/// Run a solution part in a tight loop for `--seconds <s>`, so that profilers attached to the process mostly sample solution code.
+/// Unlike `--time`, no samples are recorded and the loop only checks the clock every few runs.
+pub fn profile_part<I: Clone, R>(func: impl Fn(I) -> R, input: I, part: u8) {
+    if !is_part_selected(part) {
+        return;
+    }
+
+    let seconds = parse_seconds();
+    let _logging = logger::pause();
+
+    let timer = Instant::now();
+    let mut runs: u64 = 0;
+
+    while timer.elapsed().as_secs_f64() < seconds {
+        for _ in 0..PROFILE_BATCH {
+            hint::black_box(func(hint::black_box(input.clone())));
+        }
+        runs += PROFILE_BATCH;
+    }
+
+    let elapsed = timer.elapsed();
+    #[allow(clippy::cast_precision_loss)]
+    let per_run = Duration::from_secs_f64(elapsed.as_secs_f64() / runs as f64);
+
+    println!("Part {part}: {runs} runs in {elapsed:.1?} ({per_run:.1?} per run)");
+}
+
+/// Parse the `--seconds <s>` argument of profile runs.
+fn parse_seconds() -> f64 {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--seconds") else {
+        return DEFAULT_PROFILE_SECONDS;
+    };
+
+    match args.get(index + 1).map(|x| x.parse::<f64>()) {
+        Some(Ok(seconds)) if seconds > 0_f64 => seconds,
+        _ => {
+            eprintln!("Unexpected command-line input. Format: --seconds <s>");
+            process::exit(1);
+        }
+    }
+}
+
+/// Compares a result computed from the puzzle input with the answer recorded in `data/answers/`, if there is one.
+fn check_answer<T: Display>(result: &T, day: Day, part: u8) -> bool {
+    if !InputSource::from_args().is_puzzle_input() {
+        return true;
+    }
+
+    let Some(expected) = read_puzzle_answer(day, part) else {
+        return true;
+    };
+
+    if result.to_string() == expected {
+        return true;
+    }
+
+    if expected.contains('\n') {
+        println!("  ✘ wrong answer, expected:");
+        println!("{expected}");
+    } else {
+        println!("  ✘ wrong answer, expected {ANSI_BOLD}{expected}{ANSI_RESET}");
+    }
+
+    false
+}
+
+/// Parse the `--part <n>` argument, parts other than `n` are skipped if it is passed.
+fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(part_index) = args.iter().position(|x| x == "--part") else {
+        return true;
+    };
+
+    let Some(Ok(part_filter)) = args.get(part_index + 1).map(|x| x.parse::<u8>()) else {
+        eprintln!("Unexpected command-line input. Format: --part <1|2>");
+        process::exit(1);
+    };
+
+    part_filter == part
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    part: u8,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -37,7 +181,7 @@ fn run_timed<I: Clone, T>(
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        bench(func, input, part, &base_time)
     } else {
         (base_time, 1)
     };
@@ -45,23 +189,42 @@ fn run_timed<I: Clone, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    part: u8,
+    base_time: &Duration,
+) -> (Duration, u128) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if is_interactive() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let settings = &config::get().bench;
+    let bench_iterations = (settings.budget().as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(
+            u128::from(settings.min_samples),
+            u128::from(settings.max_samples),
+        );
 
     let mut timers: Vec<Duration> = vec![];
+    let mut last_report: Option<Instant> = None;
+    // logging would skew the samples, the first run already logged everything there is.
+    let _logging = logger::pause();
+
+    for samples in 0..bench_iterations {
+        // throttle progress reports, so that they do not skew the samples of fast solutions.
+        if progress::is_enabled() && last_report.is_none_or(|x| x.elapsed() > PROGRESS_INTERVAL) {
+            progress::report(Event::Benching {
+                part,
+                samples,
+                total: bench_iterations,
+            });
+            last_report = Some(Instant::now());
+        }
 
-    for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -76,6 +239,11 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     )
 }
 
+/// Returns `false` if stdout is redirected, intermediate results are only printed to terminals.
+fn is_interactive() -> bool {
+    stdout().is_terminal()
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -92,17 +260,24 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Answer<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    // intermediate results are overwritten once the final result is known.
+    if is_intermediate_result && !is_interactive() {
+        return;
+    }
+
+    let carriage_return = if is_interactive() { "\r" } else { "" };
+
     match result {
-        Some(result) => {
+        Answer::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    print!("{carriage_return}");
                     println!("{str}");
                     println!("{result}");
                 }
@@ -111,22 +286,226 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
+                    print!("{carriage_return}");
                     println!("{str}");
                 }
             }
         }
-        None => {
+        Answer::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
-                print!("\r");
+                print!("{carriage_return}");
                 println!("{part}: ✖             ");
             }
         }
+        // errors are printed without timings, so that `all` does not pick them up as benchmarks.
+        Answer::Error(err) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ {ANSI_BOLD}error{ANSI_RESET}");
+            } else {
+                print!("{carriage_return}");
+                println!("{part}: ✖ {ANSI_BOLD}error{ANSI_RESET}             ");
+                print_error_chain(err);
+            }
+        }
+    }
+}
+
+/// Prints an error followed by each of its causes, one per line.
+fn print_error_chain(err: &anyhow::Error) {
+    println!("  {err}");
+    for cause in err.chain().skip(1) {
+        println!("  caused by: {cause}");
+    }
+}
+
+/// The result of running a solution part against one of its example files.
+pub struct ExampleOutcome {
+    pub part: u8,
+    pub example: String,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub error: Option<anyhow::Error>,
+    pub panicked: bool,
+    pub duration: Duration,
+}
+
+impl ExampleOutcome {
+    /// An outcome passes if the part returned the recorded answer.
+    /// Examples without a recorded answer never fail.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        !self.panicked
+            && self.error.is_none()
+            && match &self.expected {
+                Some(expected) => self.actual.as_ref() == Some(expected),
+                None => true,
+            }
+    }
+}
+
+/// Run a solution part once against each of its example files and compare the results with the recorded answers.
+/// Parts that were filtered out via `--part <n>` yield no outcomes.
+pub fn test_part<R: PartResult>(
+    func: impl Fn(&str) -> R,
+    day: Day,
+    part: u8,
+) -> Vec<ExampleOutcome> {
+    if !is_part_selected(part) {
+        return vec![];
+    }
+
+    read_examples(&format!("examples/part{part}"), day)
+        .into_iter()
+        .map(|example| {
+            let timer = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| func(&example.input)));
+            let duration = timer.elapsed();
+
+            let (actual, error, panicked) = match result.map(PartResult::into_answer) {
+                Ok(Answer::Solved(result)) => (Some(result.to_string()), None, false),
+                Ok(Answer::Unsolved) => (None, None, false),
+                Ok(Answer::Error(err)) => (None, Some(err), false),
+                Err(_) => (None, None, true),
+            };
+
+            ExampleOutcome {
+                part,
+                example: example.name,
+                expected: example.answer,
+                actual,
+                error,
+                panicked,
+                duration,
+            }
+        })
+        .collect()
+}
+
+/// Print a table of example outcomes, followed by a line diff for every failing multi-line answer.
+/// Returns `true` if all examples passed.
+pub fn print_test_report(outcomes: &[ExampleOutcome]) -> bool {
+    let rows = outcomes
+        .iter()
+        .map(|outcome| {
+            let status = if outcome.passed() { "✔" } else { "✘" };
+            let actual = if outcome.panicked {
+                "panicked".to_string()
+            } else if outcome.error.is_some() {
+                "error".to_string()
+            } else {
+                format_answer(outcome.actual.as_deref())
+            };
+            [
+                status.to_string(),
+                format!("Part {}", outcome.part),
+                outcome.example.clone(),
+                format_answer(outcome.expected.as_deref()),
+                actual,
+                format!("{:.1?}", outcome.duration),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let header = ["", "Part", "Example", "Expected", "Actual", "Time"].map(String::from);
+
+    let widths = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    for (idx, row) in [&header].into_iter().chain(rows.iter()).enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        if idx == 0 {
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", line.trim_end());
+        } else {
+            println!("{}", line.trim_end());
+        }
+    }
+
+    for outcome in outcomes.iter().filter(|outcome| !outcome.passed()) {
+        if let Some(err) = &outcome.error {
+            println!();
+            println!(
+                "{ANSI_BOLD}Part {} ({}){ANSI_RESET}",
+                outcome.part, outcome.example
+            );
+            print_error_chain(err);
+        }
+
+        if let (Some(expected), Some(actual)) = (&outcome.expected, &outcome.actual) {
+            if expected.contains('\n') || actual.contains('\n') {
+                println!();
+                println!(
+                    "{ANSI_BOLD}Part {} ({}){ANSI_RESET}",
+                    outcome.part, outcome.example
+                );
+                print_line_diff(expected, actual);
+            }
+        }
+    }
+
+    let failed = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+
+    println!();
+    println!("{} passed, {failed} failed.", outcomes.len() - failed);
+
+    failed == 0
+}
+
+fn format_answer(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => "▼".into(),
+        Some(answer) => answer.into(),
+        None => "-".into(),
+    }
+}
+
+/// Prints `expected` and `actual` side by side line by line, marking differing lines with `-` / `+`.
+fn print_line_diff(expected: &str, actual: &str) {
+    let expected_lines = expected.lines().collect::<Vec<_>>();
+    let actual_lines = actual.lines().collect::<Vec<_>>();
+
+    for idx in 0..cmp::max(expected_lines.len(), actual_lines.len()) {
+        match (expected_lines.get(idx), actual_lines.get(idx)) {
+            (Some(expected), Some(actual)) if expected == actual => println!("  {expected}"),
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    println!("- {expected}");
+                }
+                if let Some(actual) = actual {
+                    println!("+ {actual}");
+                }
+            }
+        }
     }
 }
 
+/// Asks a yes/no question on stdin, anything but `y` or `yes` is a no.
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
@@ -157,6 +536,25 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if !InputSource::from_args().is_puzzle_input() {
+        eprintln!("Refusing to submit a result that was not computed from the puzzle input.");
+        process::exit(1);
+    }
+
+    match config::get().submit.policy {
+        SubmitPolicy::Allow => {}
+        SubmitPolicy::Confirm => {
+            if !confirm(&format!("Submit {result} for part {part}?")) {
+                println!("Not submitting.");
+                return None;
+            }
+        }
+        SubmitPolicy::Never => {
+            eprintln!("Refusing to submit, submissions are turned off in aoc.toml.");
+            process::exit(1);
+        }
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
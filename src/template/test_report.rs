@@ -0,0 +1,294 @@
+/// Module that renders the results of an `all` run as JUnit XML or TAP, e.g. for CI dashboards.
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{fmt, fs, io};
+
+use crate::Day;
+
+/// A report format, optionally with the file it should be written to.
+/// Reports without a path are printed to stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub format: Format,
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Junit,
+    Tap,
+}
+
+#[derive(Debug)]
+pub struct ReportFromStrError(String);
+
+impl std::error::Error for ReportFromStrError {}
+
+impl fmt::Display for ReportFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown report format \"{}\", expected `junit[=path]` or `tap[=path]`",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Report {
+    type Err = ReportFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (format, path) = match s.split_once('=') {
+            Some((format, path)) if !path.is_empty() => (format, Some(PathBuf::from(path))),
+            Some(_) => return Err(ReportFromStrError(s.to_string())),
+            None => (s, None),
+        };
+
+        let format = match format {
+            "junit" => Format::Junit,
+            "tap" => Format::Tap,
+            _ => return Err(ReportFromStrError(s.to_string())),
+        };
+
+        Ok(Self { format, path })
+    }
+}
+
+/// The result of running one part of a day's solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    pub day: Day,
+    pub part: u8,
+    pub duration: Option<Duration>,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Skipped(String),
+    Failed { message: String, body: String },
+}
+
+impl Report {
+    /// Renders the test cases and writes them to the report's path, or to stdout.
+    pub fn write(&self, cases: &[TestCase]) -> io::Result<()> {
+        let content = match self.format {
+            Format::Junit => junit(cases),
+            Format::Tap => tap(cases),
+        };
+
+        match &self.path {
+            Some(path) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)
+            }
+            None => {
+                print!("{content}");
+                Ok(())
+            }
+        }
+    }
+}
+
+fn seconds(duration: Option<Duration>) -> f64 {
+    duration.map_or(0_f64, |x| x.as_secs_f64())
+}
+
+fn junit(cases: &[TestCase]) -> String {
+    let count =
+        |pattern: fn(&Outcome) -> bool| cases.iter().filter(|x| pattern(&x.outcome)).count();
+    let failures = count(|x| matches!(x, Outcome::Failed { .. }));
+    let skipped = count(|x| matches!(x, Outcome::Skipped(_)));
+    let time = cases.iter().map(|x| seconds(x.duration)).sum::<f64>();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    let _ = writeln!(
+        xml,
+        "<testsuites tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.6}\">",
+        cases.len(),
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"advent_of_code\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.6}\">",
+        cases.len(),
+    );
+
+    for case in cases {
+        let _ = write!(
+            xml,
+            "    <testcase classname=\"day{}\" name=\"part {}\" time=\"{:.6}\"",
+            case.day,
+            case.part,
+            seconds(case.duration)
+        );
+
+        match &case.outcome {
+            Outcome::Passed => xml.push_str("/>\n"),
+            Outcome::Skipped(reason) => {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <skipped message=\"{}\"/>",
+                    escape_xml(reason)
+                );
+                xml.push_str("    </testcase>\n");
+            }
+            Outcome::Failed { message, body } => {
+                let _ = writeln!(
+                    xml,
+                    ">\n      <failure message=\"{}\">{}</failure>",
+                    escape_xml(message),
+                    escape_xml(body)
+                );
+                xml.push_str("    </testcase>\n");
+            }
+        }
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn escape_xml(s: &str) -> String {
+    s.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .fold(String::new(), |mut acc, c| {
+            match c {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '"' => acc.push_str("&quot;"),
+                '\'' => acc.push_str("&apos;"),
+                c => acc.push(c),
+            }
+            acc
+        })
+}
+
+/// Renders the cases as TAP version 13, with failure details in a YAML block.
+fn tap(cases: &[TestCase]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", cases.len());
+
+    for (i, case) in cases.iter().enumerate() {
+        let description = format!("day {} part {}", case.day, case.part);
+
+        match &case.outcome {
+            Outcome::Passed => {
+                let _ = writeln!(tap, "ok {} - {description}", i + 1);
+            }
+            Outcome::Skipped(reason) => {
+                let _ = writeln!(tap, "ok {} - {description} # SKIP {reason}", i + 1);
+            }
+            Outcome::Failed { message, body } => {
+                let _ = writeln!(tap, "not ok {} - {description}", i + 1);
+                tap.push_str("  ---\n");
+                let _ = writeln!(tap, "  message: {message:?}");
+                if !body.is_empty() {
+                    tap.push_str("  body: |\n");
+                    body.lines().for_each(|line| {
+                        let _ = writeln!(tap, "    {line}");
+                    });
+                }
+                tap.push_str("  ...\n");
+            }
+        }
+
+        if let Some(duration) = case.duration {
+            let _ = writeln!(tap, "# time: {duration:.1?}");
+        }
+    }
+
+    tap
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{junit, tap, Format, Outcome, Report, TestCase};
+    use crate::day;
+
+    fn cases() -> Vec<TestCase> {
+        vec![
+            TestCase {
+                day: day!(1),
+                part: 1,
+                duration: Some(Duration::from_millis(2)),
+                outcome: Outcome::Passed,
+            },
+            TestCase {
+                day: day!(1),
+                part: 2,
+                duration: None,
+                outcome: Outcome::Failed {
+                    message: "panicked".into(),
+                    body: "index out of bounds: 3 < 1 && \"x\"".into(),
+                },
+            },
+            TestCase {
+                day: day!(2),
+                part: 1,
+                duration: None,
+                outcome: Outcome::Skipped("unsolved".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_reports() {
+        assert_eq!(
+            "junit=target/junit.xml".parse::<Report>().unwrap(),
+            Report {
+                format: Format::Junit,
+                path: Some(PathBuf::from("target/junit.xml"))
+            }
+        );
+        assert_eq!(
+            "tap".parse::<Report>().unwrap(),
+            Report {
+                format: Format::Tap,
+                path: None
+            }
+        );
+        assert!("junit=".parse::<Report>().is_err());
+        assert!("xml".parse::<Report>().is_err());
+    }
+
+    #[test]
+    fn renders_junit() {
+        let xml = junit(&cases());
+        assert!(
+            xml.contains("<testsuites tests=\"3\" failures=\"1\" skipped=\"1\" time=\"0.002000\">")
+        );
+        assert!(xml.contains("<testcase classname=\"day01\" name=\"part 1\" time=\"0.002000\"/>"));
+        assert!(xml.contains(
+            "<failure message=\"panicked\">index out of bounds: 3 &lt; 1 &amp;&amp; &quot;x&quot;</failure>"
+        ));
+        assert!(xml.contains("<skipped message=\"unsolved\"/>"));
+    }
+
+    #[test]
+    fn renders_tap() {
+        assert_eq!(
+            tap(&cases()),
+            "TAP version 13
+1..3
+ok 1 - day 01 part 1
+# time: 2.0ms
+not ok 2 - day 01 part 2
+  ---
+  message: \"panicked\"
+  body: |
+    index out of bounds: 3 < 1 && \"x\"
+  ...
+ok 3 - day 02 part 1 # SKIP unsolved
+"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+/// Module that lets solutions render frames of their intermediate state, e.g. an animated grid.
+/// Frames are drawn to the terminal with `--viz`, or written to numbered files with `--viz-dir <dir>`:
+///  1. text frames are written as `frame-00001.txt`.
+///  2. image frames are written as binary PPM files, `frame-00001.ppm`.
+use std::fmt::Display;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, process, thread};
+
+use super::ANSI_RESET;
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// An RGB image, e.g. one pixel per grid cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Image {
+    /// Creates a black image.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0, 0, 0]; width * height],
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sets the color of a pixel, pixels outside of the image are ignored.
+    pub fn set(&mut self, x: usize, y: usize, rgb: [u8; 3]) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = rgb;
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<[u8; 3]> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Encodes the image as a binary PPM (`P6`).
+    #[must_use]
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    /// Renders the image with two pixels per character, using true color half blocks.
+    #[must_use]
+    pub fn to_terminal(&self) -> String {
+        let mut out = String::new();
+
+        for y in (0..self.height).step_by(2) {
+            for x in 0..self.width {
+                let [r, g, b] = self.pixels[y * self.width + x];
+                let [br, bg, bb] = self.get(x, y + 1).unwrap_or([0, 0, 0]);
+                out.push_str(&format!("\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀"));
+            }
+            out.push_str(ANSI_RESET);
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Terminal,
+    Directory(PathBuf),
+}
+
+/// Receives the frames of a visualization and draws or stores them.
+pub struct FrameSink {
+    target: Target,
+    frames: usize,
+    delay: Duration,
+}
+
+impl FrameSink {
+    /// Reads the sink from the arguments passed to the solution binary.
+    /// Returns [`None`] if no visualization was requested.
+    #[must_use]
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+
+        let target = if let Some(index) = args.iter().position(|x| x == "--viz-dir") {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --viz-dir <dir>");
+                process::exit(1);
+            };
+            Target::Directory(path.into())
+        } else if args.iter().any(|x| x == "--viz") {
+            Target::Terminal
+        } else {
+            return None;
+        };
+
+        // visualizations are slow by design and would render every sample.
+        if args.iter().any(|x| x == "--time") {
+            eprintln!("Visualizations can not be benched, remove `--time` to run them.");
+            process::exit(1);
+        }
+
+        Some(Self::new(target))
+    }
+
+    fn new(target: Target) -> Self {
+        if let Target::Directory(path) = &target {
+            fs::create_dir_all(path).expect("could not create frame directory");
+        }
+
+        Self {
+            target,
+            frames: 0,
+            delay: DEFAULT_DELAY,
+        }
+    }
+
+    /// Sets how long each frame is shown in the terminal.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// The number of frames that were emitted so far.
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Emits a frame of text, e.g. a rendered grid.
+    pub fn text(&mut self, frame: impl Display) {
+        let frame = frame.to_string();
+        self.emit(|| frame.clone(), || frame.clone().into_bytes(), "txt");
+    }
+
+    /// Emits an image frame.
+    pub fn image(&mut self, image: &Image) {
+        self.emit(|| image.to_terminal(), || image.to_ppm(), "ppm");
+    }
+
+    fn emit(
+        &mut self,
+        screen: impl FnOnce() -> String,
+        file: impl FnOnce() -> Vec<u8>,
+        extension: &str,
+    ) {
+        self.frames += 1;
+
+        match &self.target {
+            Target::Terminal => {
+                let mut stdout = stdout().lock();
+                // clear the screen and move the cursor to the top left corner.
+                let _ = write!(stdout, "\x1b[2J\x1b[H{}", screen());
+                let _ = writeln!(stdout, "\nFrame {}", self.frames);
+                let _ = stdout.flush();
+                thread::sleep(self.delay);
+            }
+            Target::Directory(path) => {
+                let path = path.join(format!("frame-{:05}.{extension}", self.frames));
+                fs::write(path, file()).expect("could not write frame");
+            }
+        }
+    }
+
+    /// Reports where the frames went once the visualization is done.
+    pub fn finish(self) {
+        match self.target {
+            Target::Terminal => println!("Rendered {} frames.", self.frames),
+            Target::Directory(path) => {
+                println!("Wrote {} frames to {}.", self.frames, path.display());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{FrameSink, Image, Target};
+
+    #[test]
+    fn encodes_ppm() {
+        let mut image = Image::new(2, 1);
+        image.set(1, 0, [255, 128, 0]);
+        image.set(5, 5, [1, 1, 1]);
+
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x00\x00\x00\xff\x80\x00");
+        assert_eq!(image.get(1, 0), Some([255, 128, 0]));
+        assert_eq!(image.get(2, 0), None);
+    }
+
+    #[test]
+    fn writes_numbered_frames() {
+        let dir = env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut sink = FrameSink::new(Target::Directory(dir.clone()));
+
+        sink.text("#.\n.#");
+        sink.image(&Image::new(1, 1));
+
+        assert_eq!(sink.frames(), 2);
+        assert_eq!(
+            fs::read_to_string(dir.join("frame-00001.txt")).unwrap(),
+            "#.\n.#"
+        );
+        assert!(dir.join("frame-00002.ppm").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}